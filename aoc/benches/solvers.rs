@@ -0,0 +1,56 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+// the runner is a binary crate, so borrow its registry module directly
+#[path = "../src/registry.rs"]
+mod registry;
+
+/// Benchmark every registered solver against the example and personal inputs of its day.
+///
+/// The benchmark ids are like `day14 part2/ruksi.txt`, so e.g. `cargo bench -- day14` runs
+/// only the day 14 benchmarks. Inputs the solver can't solve, e.g. an example that is only
+/// meant for the other part, are skipped. Comparing against a baseline is up to criterion's
+/// `--save-baseline` and `--baseline`, which the pull request checks use too, see the README.
+fn solvers(c: &mut Criterion) {
+    let registry = registry::registry();
+    for solver in registry.iter() {
+        let mut group = c.benchmark_group(format!("day{:02} part{}", solver.day(), solver.part()));
+        for input_path in input_paths(solver.day()) {
+            let input_name = input_path.file_name().unwrap_or_default().to_string_lossy();
+            let Ok(input) = basics::read_input_file(&input_path) else { continue; };
+            if solver.solve(input.clone()).is_err() {
+                continue;
+            }
+            group.bench_function(input_name.as_ref(), |b| {
+                b.iter_batched(|| input.clone(), |input| solver.solve(input), BatchSize::SmallInput)
+            });
+        }
+        group.finish();
+    }
+}
+
+/// The `examples/example*.txt` and `examples/<user>.txt` inputs of the day, sorted by name.
+fn input_paths(day: u8) -> Vec<PathBuf> {
+    let day_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(format!("day{:02}", day));
+    let Ok(entries) = fs::read_dir(day_dir.join("examples")) else { return vec![]; };
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            name.ends_with(".txt") && name.contains("example")
+        })
+        .chain(basics::user_inputs(&day_dir))
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+}
+
+criterion_group! {
+    name = benches;
+    // a few of the solvers take seconds per run, keep the whole suite bearable
+    config = Criterion::default().sample_size(10).measurement_time(Duration::from_secs(3));
+    targets = solvers
+}
+criterion_main!(benches);
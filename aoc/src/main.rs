@@ -0,0 +1,122 @@
+use std::env;
+use std::path::Path;
+use std::process::ExitCode;
+use basics::{Answers, Args, Verdict};
+
+mod registry;
+mod scaffold;
+
+const USAGE: &str = "\
+Usage:
+    aoc list                             list all the available solvers, with their own options
+    aoc run <day> <part> [options] <input-file>...
+                                         run one solver on the given input files, - for stdin,
+                                         also taking its own options, e.g. 12 2 --factor 20000
+    aoc run --all                        run all solvers on every dayXX/examples/<user>.txt,
+                                         reporting disagreements with the <user>.answers
+    aoc verify                           check all solvers against the dayXX/answers.txt and
+                                         the dayXX/examples/<user>.answers
+    aoc new <day> [<title>...]           create the dayXX crate of a new day from the templates,
+                                         registering it in the workspace and the runner
+
+Options:
+    --raw                                don't normalize the inputs, e.g. CRLF to LF
+    --strip-trailing-whitespace          strip trailing whitespace from each input line
+    --time                               report the parse and solve times to stderr
+    --bench <runs>                       solve each input <runs> times and report min/median/max";
+
+fn main() -> ExitCode {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+    let result = match args.as_slice() {
+        ["list"] => list(),
+        ["run", "--all"] => run_all(),
+        ["verify"] => verify(),
+        ["new", day, title @ ..] => new_day(day, title),
+        ["run", day, part, rest @ ..] if !rest.is_empty() => run_one(day, part, rest),
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn list() -> Result<(), String> {
+    for solver in registry::registry().iter() {
+        let options = solver.options().iter().map(|option| format!(" [{} {}]", option.name, option.value));
+        println!("day{:02} part{}: {}{}", solver.day(), solver.part(), solver.title(), options.collect::<String>());
+    }
+    Ok(())
+}
+
+fn run_one(day: &str, part: &str, rest: &[&str]) -> Result<(), String> {
+    let day = day.parse::<u8>().map_err(|_e| format!("Invalid day: {}", day))?;
+    let part = part.parse::<u8>().map_err(|_e| format!("Invalid part: {}", part))?;
+    let registry = registry::registry();
+    let solver = registry
+        .find(day, part)
+        .ok_or(format!("No solver for day {} part {}", day, part))?;
+    let args = Args::parse_with(rest, solver.options()).map_err(|e| format!("{}\n\n{}", e, basics::solver_usage(solver)))?;
+    basics::run_with(solver, &args)
+}
+
+fn new_day(day: &str, title: &[&str]) -> Result<(), String> {
+    let day = match day.parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => day,
+        _ => return Err(format!("Invalid day: {}", day)),
+    };
+    let title = if title.is_empty() { "Untitled".to_string() } else { title.join(" ") };
+    scaffold::new_day(Path::new("."), day, &title)?;
+    println!("Created day{:02}, rise and shine! ☀️", day);
+    Ok(())
+}
+
+fn run_all() -> Result<(), String> {
+    let args = Args::parse(["--users"])?;
+    let mut problems = vec![];
+    for solver in registry::registry().iter() {
+        let label = format!("day{:02} part{}", solver.day(), solver.part());
+        let day_dir = format!("day{:02}", solver.day());
+        if basics::user_inputs(Path::new(&day_dir)).is_empty() {
+            println!("{}: skipped, no {}/examples/<user>.txt", label, day_dir);
+            continue;
+        }
+        println!("{}:", label);
+        if let Err(e) = basics::run_with(solver, &args) {
+            problems.push(format!("{}: {}", label, e));
+        }
+    }
+    match problems.is_empty() {
+        true => Ok(()),
+        false => Err(problems.join("\n")),
+    }
+}
+
+fn verify() -> Result<(), String> {
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for solver in registry::registry().iter() {
+        let label = format!("day{:02} part{}", solver.day(), solver.part());
+        let day_dir = format!("day{:02}", solver.day());
+        let answers = Answers::discover(Path::new(&day_dir))?;
+        for (input, verdict) in basics::verify(solver, Path::new(&day_dir), &answers) {
+            println!("{} {}: {}", label, input, verdict);
+            match verdict {
+                Verdict::Pass => passed += 1,
+                Verdict::Missing => missing += 1,
+                _ => failed += 1,
+            }
+        }
+    }
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{} answer(s) failed", failed)),
+    }
+}
@@ -0,0 +1,24 @@
+use basics::Registry;
+
+/// Collect the solvers of every day into one registry.
+pub fn registry() -> Registry {
+    let mut registry = Registry::new();
+    day01::register(&mut registry);
+    day02::register(&mut registry);
+    day03::register(&mut registry);
+    day04::register(&mut registry);
+    day05::register(&mut registry);
+    day06::register(&mut registry);
+    day07::register(&mut registry);
+    day08::register(&mut registry);
+    day09::register(&mut registry);
+    day10::register(&mut registry);
+    day11::register(&mut registry);
+    day12::register(&mut registry);
+    day13::register(&mut registry);
+    day14::register(&mut registry);
+    day15::register(&mut registry);
+    day16::register(&mut registry);
+    day18::register(&mut registry);
+    registry
+}
@@ -0,0 +1,221 @@
+use std::fs;
+use std::path::Path;
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.template");
+const LIB_RS: &str = include_str!("../templates/lib.rs.template");
+const BIN_RS: &str = include_str!("../templates/bin.rs.template");
+const README_MD: &str = include_str!("../templates/README.md.template");
+const ANSWERS_TXT: &str = include_str!("../templates/answers.txt.template");
+
+/// Create the crate of a new day under the workspace root from the templates, and register
+/// it in the workspace members, the runner dependencies and the solver registry.
+///
+/// Refuses to touch anything if the day already exists, in any of those places.
+pub fn new_day(root: &Path, day: u8, title: &str) -> Result<(), String> {
+    let name = format!("day{:02}", day);
+    let day_dir = root.join(&name);
+    if day_dir.exists() {
+        return Err(format!("{} already exists, not overwriting it", day_dir.display()));
+    }
+
+    // prepare all the edits first, so a failure leaves the workspace as it was
+    let workspace_path = root.join("Cargo.toml");
+    let runner_path = root.join("aoc").join("Cargo.toml");
+    let registry_path = root.join("aoc").join("src").join("registry.rs");
+    let workspace = add_workspace_member(&read(&workspace_path)?, &name)
+        .map_err(|e| format!("{}: {}", workspace_path.display(), e))?;
+    let runner = add_runner_dependency(&read(&runner_path)?, &name)
+        .map_err(|e| format!("{}: {}", runner_path.display(), e))?;
+    let registry = add_registration(&read(&registry_path)?, &name)
+        .map_err(|e| format!("{}: {}", registry_path.display(), e))?;
+
+    // stage the day next to where it goes, so it appears whole or not at all
+    let staging_dir = root.join(format!(".{}.new", name));
+    let staged = stage_day(&staging_dir, &name, day, title).and_then(|()| {
+        fs::rename(&staging_dir, &day_dir).map_err(|e| format!("{}: {}", day_dir.display(), e))
+    });
+    if let Err(e) = staged {
+        let _ = fs::remove_dir_all(&staging_dir);
+        return Err(e);
+    }
+
+    // then the registrations, putting back the ones done when one of them fails
+    let edits = [(&workspace_path, workspace), (&runner_path, runner), (&registry_path, registry)];
+    let mut originals = vec![];
+    for (path, contents) in edits {
+        let original = read(path);
+        if let Err(e) = original.and_then(|original| {
+            write_atomically(path, &contents)?;
+            originals.push((path, original));
+            Ok(())
+        }) {
+            for (path, original) in originals {
+                let _ = write_atomically(path, &original);
+            }
+            let _ = fs::remove_dir_all(&day_dir);
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+/// Write the files of the day from the templates into the directory.
+fn stage_day(dir: &Path, name: &str, day: u8, title: &str) -> Result<(), String> {
+    let _ = fs::remove_dir_all(dir); // left over from a crashed attempt
+    let bin_dir = dir.join("src").join("bin");
+    fs::create_dir_all(&bin_dir).map_err(|e| format!("{}: {}", bin_dir.display(), e))?;
+    fs::create_dir_all(dir.join("examples")).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    let fill = |template: &str| fill(template, name, day, title);
+    write(&dir.join("Cargo.toml"), &fill(CARGO_TOML))?;
+    write(&dir.join("README.md"), &fill(README_MD))?;
+    write(&dir.join(basics::ANSWERS_FILE), &fill(ANSWERS_TXT))?;
+    write(&dir.join("examples").join("example.txt"), "")?;
+    write(&dir.join("src").join("lib.rs"), &fill(LIB_RS))?;
+    for part in 1..=2 {
+        let bin = fill(BIN_RS).replace("{{part}}", &part.to_string());
+        write(&bin_dir.join(format!("{}-part{}.rs", name, part)), &bin)?;
+    }
+    Ok(())
+}
+
+/// Fill in the template, the title as it is for the docs and as a string literal for the code.
+fn fill(template: &str, name: &str, day: u8, title: &str) -> String {
+    template
+        .replace("{{crate}}", name)
+        .replace("{{day}}", &day.to_string())
+        .replace("{{title-literal}}", &format!("{:?}", title))
+        .replace("{{title}}", title)
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+// through a temporary file next to it, so the file is never left half written
+fn write_atomically(path: &Path, contents: &str) -> Result<(), String> {
+    let temporary_path = path.with_extension("new");
+    write(&temporary_path, contents)?;
+    fs::rename(&temporary_path, path).map_err(|e| {
+        let _ = fs::remove_file(&temporary_path);
+        format!("{}: {}", path.display(), e)
+    })
+}
+
+/// Add the crate to the `members` list of the workspace `Cargo.toml`.
+fn add_workspace_member(manifest: &str, name: &str) -> Result<String, String> {
+    let start = manifest.find("members = [").ok_or("No workspace members list")?;
+    let end = start + manifest[start..].find(']').ok_or("Unterminated workspace members list")?;
+    let mut members = manifest[start + "members = [".len()..end]
+        .split(',')
+        .map(|member| member.trim().trim_matches('"').to_string())
+        .filter(|member| !member.is_empty())
+        .collect::<Vec<_>>();
+    if members.iter().any(|member| member == name) {
+        return Err(format!("{} is already a workspace member", name));
+    }
+    members.push(name.to_string());
+    members.sort();
+    let members = members.iter().map(|member| format!("    \"{}\",\n", member)).collect::<String>();
+    Ok(format!("{}members = [\n{}{}", &manifest[..start], members, &manifest[end..]))
+}
+
+/// Add the crate as a path dependency of the runner, next to the other days.
+fn add_runner_dependency(manifest: &str, name: &str) -> Result<String, String> {
+    let line = format!("{} = {{ path = \"../{}\" }}", name, name);
+    insert_among_days(manifest, &line, |line| line.starts_with("day"))
+}
+
+/// Add the registration of the day's solvers to the runner's registry.
+fn add_registration(registry: &str, name: &str) -> Result<String, String> {
+    let line = format!("    {}::register(&mut registry);", name);
+    insert_among_days(registry, &line, |line| line.trim_start().starts_with("day"))
+}
+
+/// Insert the line in order among the consecutive lines about the days, or after
+/// the line about the basics when there are no days yet.
+fn insert_among_days(text: &str, line: &str, is_day: impl Fn(&str) -> bool) -> Result<String, String> {
+    let mut lines = text.lines().collect::<Vec<_>>();
+    if lines.contains(&line) {
+        return Err(format!("Already has '{}'", line.trim()));
+    }
+    let days = lines.iter().enumerate().filter(|(_, l)| is_day(l)).map(|(i, _)| i).collect::<Vec<_>>();
+    let index = match (days.first(), days.last()) {
+        (Some(&first), Some(&last)) => (first..=last).find(|&i| lines[i] > line).unwrap_or(last + 1),
+        _ => {
+            let anchor = lines.iter().position(|l| l.contains("Registry::new()") || l.starts_with("basics"));
+            anchor.ok_or("Nowhere to add the day")? + 1
+        }
+    };
+    lines.insert(index, line);
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filling_in_titles() {
+        let title = r#"The "Floor" Will Be \ Lava"#;
+        assert_eq!(fill("# Day {{day}}: {{title}}", "day16", 16, title), r#"# Day 16: The "Floor" Will Be \ Lava"#);
+        let code = fill("const TITLE: &str = {{title-literal}};", "day16", 16, title);
+        assert_eq!(code, r#"const TITLE: &str = "The \"Floor\" Will Be \\ Lava";"#);
+    }
+
+    #[test]
+    fn failing_to_scaffold_leaves_nothing_behind() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let workspace = "[workspace]\nmembers = [\"aoc\"]\n";
+        let runner = "[dependencies]\nbasics = { path = \"../basics\" }\n";
+        let registry = "    let mut registry = Registry::new();\n    registry\n";
+        let setup = fs::create_dir_all(root.join("aoc").join("src"))
+            .and_then(|()| fs::write(root.join("Cargo.toml"), workspace))
+            .and_then(|()| fs::write(root.join("aoc").join("Cargo.toml"), runner))
+            .and_then(|()| fs::write(root.join("aoc").join("src").join("registry.rs"), registry))
+            // in the way of writing the registry, the last of the edits
+            .and_then(|()| fs::create_dir(root.join("aoc").join("src").join("registry.new")));
+        assert!(setup.is_ok());
+
+        assert!(new_day(&root, 17, "Clumsy Crucible").is_err());
+        assert!(!root.join("day17").exists() && !root.join(".day17.new").exists());
+        assert_eq!(fs::read_to_string(root.join("Cargo.toml")).ok().as_deref(), Some(workspace));
+        assert_eq!(fs::read_to_string(root.join("aoc").join("Cargo.toml")).ok().as_deref(), Some(runner));
+
+        assert!(fs::remove_dir(root.join("aoc").join("src").join("registry.new")).is_ok());
+        assert_eq!(new_day(&root, 17, "Clumsy Crucible"), Ok(()));
+        assert!(root.join("day17").join("src").join("lib.rs").exists() && !root.join(".day17.new").exists());
+        let registered = fs::read_to_string(root.join("aoc").join("src").join("registry.rs")).unwrap_or_default();
+        assert!(registered.contains("day17::register(&mut registry);"));
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn adding_workspace_members() -> Result<(), String> {
+        let manifest = "[workspace]\nresolver = \"2\"\nmembers = [\"aoc\", \"day03\",\n  \"basics\"]\n";
+        let expected = "[workspace]\nresolver = \"2\"\nmembers = [\n    \"aoc\",\n    \"basics\",\n    \"day02\",\n    \"day03\",\n]\n";
+        assert_eq!(add_workspace_member(manifest, "day02")?, expected);
+        assert!(add_workspace_member(manifest, "day03").is_err());
+        assert!(add_workspace_member("[workspace]\n", "day02").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn adding_days_to_the_runner() -> Result<(), String> {
+        let manifest = "[dependencies]\nbasics = { path = \"../basics\" }\nday01 = { path = \"../day01\" }\nday03 = { path = \"../day03\" }\n\n[dev-dependencies]\n";
+        let added = add_runner_dependency(manifest, "day02")?;
+        assert!(added.contains("day01 = { path = \"../day01\" }\nday02 = { path = \"../day02\" }\nday03"));
+        let added = add_runner_dependency(manifest, "day04")?;
+        assert!(added.contains("day03 = { path = \"../day03\" }\nday04 = { path = \"../day04\" }\n\n"));
+        assert!(add_runner_dependency(manifest, "day03").is_err());
+
+        let registry = "use basics::Registry;\n\n    let mut registry = Registry::new();\n    registry\n";
+        let added = add_registration(registry, "day01")?;
+        assert!(added.ends_with("Registry::new();\n    day01::register(&mut registry);\n    registry\n"));
+        Ok(())
+    }
+}
@@ -0,0 +1,314 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::{read_input_file, Error, Solver};
+
+/// The name of the answers file in each day's directory.
+pub const ANSWERS_FILE: &str = "answers.txt";
+
+/// The extension of the sidecar answers file next to a user input, e.g. `examples/ruksi.answers`.
+pub const SIDECAR_EXTENSION: &str = "answers";
+
+/// The known answers of a day's inputs, loaded from the day's `answers.txt`.
+///
+/// Each line has an input path relative to the day's directory, and the answers of
+/// part 1 and part 2, `-` when the answer is not known:
+///
+/// ```text
+/// # input                 part1    part2
+/// examples/example.txt    142      -
+/// examples/example2.txt   -        281
+/// ```
+///
+/// Empty lines and lines starting with `#` are ignored. The answers of the user inputs
+/// live next to them instead, see [`Answers::discover`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Answers(Vec<Expected>);
+
+/// The known answers of one input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Expected {
+    /// Load the answers of `input` from its sidecar file, with lines like `part1 56397`.
+    pub fn load_sidecar(input: String, sidecar_path: &Path) -> Result<Self, Error> {
+        Self::from_sidecar_text(input, &read_input_file(sidecar_path)?)
+    }
+
+    pub fn from_sidecar_text(input: String, text: &str) -> Result<Self, Error> {
+        let mut expected = Self { input, part1: None, part2: None };
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let answer = match fields[..] {
+                [_, "-"] => None,
+                [_, answer] => Some(answer.to_string()),
+                _ => return Err(Error::parse_at(text, line, "Sidecar line is not like 'part1 <answer>'")),
+            };
+            match fields[0] {
+                "part1" => expected.part1 = answer,
+                "part2" => expected.part2 = answer,
+                other => return Err(Error::parse_at(text, other, format!("Unknown part: {}", other))),
+            }
+        }
+        Ok(expected)
+    }
+
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl Answers {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::from_text(&read_input_file(path)?)
+    }
+
+    /// Load all the known answers of a day: the `answers.txt`, if any, and every
+    /// user input `examples/<user>.txt` with the answers of its sidecar, if any.
+    ///
+    /// User inputs without a sidecar are included without answers, so they show up as missing.
+    pub fn discover(day_dir: &Path) -> Result<Self, Error> {
+        let answers_path = day_dir.join(ANSWERS_FILE);
+        let mut answers = match answers_path.exists() {
+            true => Self::load(&answers_path).map_err(|e| in_file(e, &answers_path))?,
+            false => Self::default(),
+        };
+        for user_input in user_inputs(day_dir) {
+            let name = user_input.file_name().unwrap_or_default().to_string_lossy();
+            let input = format!("examples/{}", name);
+            let expected = match sidecar_answers(&user_input)? {
+                Some(expected) => Expected { input, ..expected },
+                None => Expected { input, part1: None, part2: None },
+            };
+            answers.0.retain(|e| e.input != expected.input);
+            answers.0.push(expected);
+        }
+        Ok(answers)
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, Error> {
+        let mut answers = vec![];
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let [input, part1, part2] = fields[..] else {
+                let message = "Answers line is not like '<input> <part1> <part2>'";
+                return Err(Error::parse_at(text, line, message));
+            };
+            let known = |answer: &str| if answer == "-" { None } else { Some(answer.to_string()) };
+            answers.push(Expected { input: input.to_string(), part1: known(part1), part2: known(part2) });
+        }
+        Ok(Self(answers))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=&Expected> {
+        self.0.iter()
+    }
+
+    /// The known answer of the given input and part, if any.
+    pub fn expected(&self, input: &str, part: u8) -> Option<&str> {
+        self.iter().find(|e| e.input == input).and_then(|e| e.part(part))
+    }
+}
+
+/// The directory of the day, either `dayXX` under the working directory, or the working
+/// directory itself, e.g. when running the day's tests.
+pub fn day_dir(day: u8) -> PathBuf {
+    let dir = PathBuf::from(format!("day{:02}", day));
+    if dir.is_dir() { dir } else { PathBuf::from(".") }
+}
+
+/// The user inputs of a day, i.e. the `examples/<user>.txt` files that aren't challenge examples.
+pub fn user_inputs(day_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(day_dir.join("examples")) else { return vec![]; };
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            name.ends_with(".txt") && !name.contains("example")
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+}
+
+/// The sidecar answers files of a day that have no user input next to them, e.g. left behind
+/// by a renamed input.
+pub fn orphan_sidecars(day_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(day_dir.join("examples")) else { return vec![]; };
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == SIDECAR_EXTENSION))
+        .filter(|path| !path.with_extension("txt").exists())
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+}
+
+/// The answers of the input at `path`, from its sidecar file, if it has one.
+pub fn sidecar_answers(path: &Path) -> Result<Option<Expected>, Error> {
+    let sidecar_path = path.with_extension(SIDECAR_EXTENSION);
+    if !sidecar_path.exists() {
+        return Ok(None);
+    }
+    let input = path.to_string_lossy().to_string();
+    Expected::load_sidecar(input, &sidecar_path).map(Some).map_err(|e| in_file(e, &sidecar_path))
+}
+
+// answers files are not the input, so name them in the error for diagnostics
+fn in_file(error: Error, path: &Path) -> Error {
+    match error {
+        Error::Io(_) => error,
+        _ => Error::io(format!("{}: {}", path.display(), error)),
+    }
+}
+
+/// The outcome of checking a solver against a known answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String, actual: String },
+    Error(Error),
+    /// There is no known answer to compare to, so the solver wasn't run.
+    Missing,
+}
+
+impl Verdict {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Fail { .. } | Verdict::Error(_))
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected, actual } => {
+                write!(f, "FAIL, expected {} but got {}", expected, actual)
+            }
+            Verdict::Error(e) => write!(f, "FAIL, {}", e),
+            Verdict::Missing => write!(f, "missing"),
+        }
+    }
+}
+
+/// Run the solver on each input of the answers, relative to `day_dir`, and compare.
+pub fn verify(solver: &dyn Solver, day_dir: &Path, answers: &Answers) -> Vec<(String, Verdict)> {
+    answers
+        .iter()
+        .map(|expected| {
+            let Some(answer) = expected.part(solver.part()) else {
+                return (expected.input.clone(), Verdict::Missing);
+            };
+            let result = read_input_file(day_dir.join(&expected.input))
+                .and_then(|input| solver.solve(input));
+            let verdict = match result {
+                Err(e) => Verdict::Error(e),
+                Ok(actual) if actual == answer => Verdict::Pass,
+                Ok(actual) => Verdict::Fail { expected: answer.to_string(), actual },
+            };
+            (expected.input.clone(), verdict)
+        })
+        .collect()
+}
+
+/// Check the solver against the answers discovered in the working directory, for the day tests.
+///
+/// Fails on wrong answers and errors, on user inputs without a sidecar and sidecars without
+/// an input, and when not a single answer was checked; only a missing answer is fine.
+pub fn check_answers(solver: &dyn Solver) -> Result<(), String> {
+    let day_dir = Path::new(".");
+    let answers = Answers::discover(day_dir)?;
+    let verdicts = verify(solver, day_dir, &answers);
+    let mut failures = verdicts
+        .iter()
+        .filter(|(_, verdict)| verdict.is_failure())
+        .map(|(input, verdict)| format!("part{} {}: {}", solver.part(), input, verdict))
+        .collect::<Vec<_>>();
+    for user_input in user_inputs(day_dir) {
+        if !user_input.with_extension(SIDECAR_EXTENSION).exists() {
+            failures.push(format!("{}: no sidecar answers file", user_input.display()));
+        }
+    }
+    for sidecar in orphan_sidecars(day_dir) {
+        failures.push(format!("{}: no input for the sidecar answers file", sidecar.display()));
+    }
+    if !verdicts.iter().any(|(_, verdict)| *verdict == Verdict::Pass) {
+        failures.push(format!("part{}: no answers were checked", solver.part()));
+    }
+    match failures.len() {
+        0 => Ok(()),
+        _ => Err(failures.join("\n")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Puzzle;
+    use super::*;
+
+    fn shout(input: String) -> Result<String, Error> {
+        Ok(input.to_uppercase())
+    }
+
+    #[test]
+    fn parsing_answers() -> Result<(), String> {
+        let answers = Answers::from_text("# input part1 part2\n\nexamples/a.txt  142 -\nb.txt 1 2")?;
+        assert_eq!(answers.iter().count(), 2);
+        assert_eq!(answers.expected("examples/a.txt", 1), Some("142"));
+        assert_eq!(answers.expected("examples/a.txt", 2), None);
+        assert_eq!(answers.expected("b.txt", 2), Some("2"));
+        assert_eq!(answers.expected("c.txt", 1), None);
+
+        let error = Error::parse(2, 1, "Answers line is not like '<input> <part1> <part2>'");
+        assert_eq!(Answers::from_text("a.txt 1 2\nb.txt 1"), Err(error));
+        Ok(())
+    }
+
+    #[test]
+    fn parsing_sidecar_answers() -> Result<(), String> {
+        let expected = Expected::from_sidecar_text("alice.txt".to_string(), "# alice\npart1 42\npart2 -")?;
+        assert_eq!(expected, Expected { input: "alice.txt".to_string(), part1: Some("42".to_string()), part2: None });
+
+        let error = Error::parse(2, 1, "Unknown part: part3");
+        assert_eq!(Expected::from_sidecar_text("bob.txt".to_string(), "part1 1\npart3 3"), Err(error));
+        let error = Error::parse(1, 1, "Sidecar line is not like 'part1 <answer>'");
+        assert_eq!(Expected::from_sidecar_text("bob.txt".to_string(), "part1"), Err(error));
+        Ok(())
+    }
+
+    #[test]
+    fn verifying_answers() -> Result<(), String> {
+        // the basics crate has no inputs of its own, so use its own files 🤷
+        let answers = Answers::from_text("Cargo.toml - -\nsrc/lib.rs NOPE -\nnope.txt 1 -")?;
+        let verdicts = verify(&Puzzle::new(1, 1, "Shout", shout), Path::new("."), &answers);
+        assert_eq!(verdicts[0].1, Verdict::Missing);
+        assert!(matches!(verdicts[1].1, Verdict::Fail { .. }));
+        assert!(matches!(verdicts[2].1, Verdict::Error(Error::Io(_))));
+        assert!(verdicts[1].1.is_failure() && !verdicts[0].1.is_failure());
+        Ok(())
+    }
+
+    #[test]
+    fn checking_nothing_fails() {
+        // no answers here either, so nothing gets checked
+        let result = check_answers(&Puzzle::new(1, 1, "Shout", shout));
+        assert_eq!(result, Err("part1: no answers were checked".to_string()));
+        assert!(orphan_sidecars(Path::new(".")).is_empty());
+    }
+}
@@ -0,0 +1,162 @@
+use std::env;
+use crate::{Error, Normalization, Solver, SolverOption};
+
+/// The usage of the command line arguments shared by all the solver binaries.
+pub const ARGS_USAGE: &str = "\
+Usage: <binary> [options] <input-file>...
+
+Solves each input file in sequence, - reads the input from stdin.
+
+Options:
+    --raw                          don't normalize the inputs, e.g. CRLF to LF
+    --strip-trailing-whitespace    strip trailing whitespace from each input line
+    --time                         report the parse and solve times to stderr
+    --bench <runs>                 solve each input <runs> times and report min/median/max
+    --users                        also solve all the examples/<user>.txt inputs of the day,
+                                   reporting the ones disagreeing with their <user>.answers";
+
+/// The command line arguments shared by all the solver binaries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Args {
+    pub input_paths: Vec<String>,
+    pub normalization: Normalization,
+    pub time: bool,
+    pub bench: Option<usize>,
+    pub users: bool,
+    /// The extra options of the solver given, as `(name, value)` pairs, e.g. `--factor 20000`.
+    pub options: Vec<(String, String)>,
+}
+
+impl Args {
+    pub fn from_env() -> Result<Self, Error> {
+        Self::parse(env::args().skip(1))
+    }
+
+    pub fn parse<S: AsRef<str>>(args: impl IntoIterator<Item=S>) -> Result<Self, Error> {
+        Self::parse_with(args, &[])
+    }
+
+    /// Parse the arguments, also taking the extra options of a solver.
+    pub fn parse_with<S: AsRef<str>>(args: impl IntoIterator<Item=S>, solver_options: &[SolverOption]) -> Result<Self, Error> {
+        let mut input_paths = vec![];
+        let mut normalization = Normalization::default();
+        let mut time = false;
+        let mut bench = None;
+        let mut users = false;
+        let mut options = vec![];
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_ref() {
+                "--raw" => normalization = Normalization::none(),
+                "--strip-trailing-whitespace" => normalization.trailing_whitespace = true,
+                "--time" => time = true,
+                "--users" => users = true,
+                "--bench" => {
+                    let runs = args.next().ok_or_else(|| Error::usage("Please provide the --bench runs"))?;
+                    let runs = runs.as_ref();
+                    match runs.parse::<usize>() {
+                        Ok(runs) if runs > 0 => bench = Some(runs),
+                        _ => return Err(Error::usage(format!("Invalid --bench runs: {}", runs))),
+                    }
+                }
+                option if solver_options.iter().any(|o| o.name == option) => {
+                    let value = args.next().ok_or_else(|| Error::usage(format!("Please provide the {} value", option)))?;
+                    options.push((option.to_string(), value.as_ref().to_string()));
+                }
+                option if option.starts_with("--") => {
+                    return Err(Error::usage(format!("Unknown option: {}", option)));
+                }
+                path => input_paths.push(path.to_string()),
+            }
+        }
+        if input_paths.is_empty() && !users {
+            return Err(Error::usage("Please provide input file path(s), or - for stdin"));
+        }
+        Ok(Self { input_paths, normalization, time, bench, users, options })
+    }
+}
+
+/// The usage of the command line arguments of a solver binary, with the solver's extra options.
+pub fn solver_usage(solver: &dyn Solver) -> String {
+    if solver.options().is_empty() {
+        return ARGS_USAGE.to_string();
+    }
+    let options = solver
+        .options()
+        .iter()
+        .map(|option| {
+            let help = option.help.replace('\n', &format!("\n{:35}", ""));
+            format!("    {:<31}{}", format!("{} {}", option.name, option.value), help)
+        })
+        .collect::<Vec<_>>();
+    format!("{}\n\nDay {} part {} options:\n{}", ARGS_USAGE, solver.day(), solver.part(), options.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_args() -> Result<(), String> {
+        let args = Args::parse(["a.txt", "-", "--strip-trailing-whitespace"])?;
+        assert_eq!(args.input_paths, vec!["a.txt", "-"]);
+        assert!(args.normalization.trailing_whitespace);
+        assert!(args.normalization.crlf);
+
+        let args = Args::parse(["--raw", "a.txt"])?;
+        assert_eq!(args.normalization, Normalization::none());
+
+        let args = Args::parse(["--time", "a.txt", "--bench", "10"])?;
+        assert!(args.time);
+        assert_eq!(args.bench, Some(10));
+
+        let args = Args::parse(["--users"])?;
+        assert!(args.users && args.input_paths.is_empty());
+
+        assert_eq!(Args::parse(["--bench", "0", "a.txt"]), Err(Error::usage("Invalid --bench runs: 0")));
+        assert!(Args::parse(["a.txt", "--bench"]).is_err());
+        assert!(Args::parse(["--nope", "a.txt"]).is_err());
+        assert!(Args::parse(Vec::<String>::new()).is_err());
+        Ok(())
+    }
+
+    const FACTOR: SolverOption = SolverOption {
+        name: "--factor",
+        value: "<copies>",
+        help: "unfold the rows into this many copies\ninstead of 5",
+    };
+
+    #[test]
+    fn parsing_solver_options() -> Result<(), String> {
+        let args = Args::parse_with(["--factor", "7", "a.txt", "--time", "--factor", "8"], &[FACTOR])?;
+        assert_eq!(args.input_paths, vec!["a.txt"]);
+        assert!(args.time);
+        let options = vec![("--factor".to_string(), "7".to_string()), ("--factor".to_string(), "8".to_string())];
+        assert_eq!(args.options, options);
+
+        assert_eq!(Args::parse(["--factor", "7", "a.txt"]), Err(Error::usage("Unknown option: --factor")));
+        assert_eq!(Args::parse_with(["a.txt", "--factor"], &[FACTOR]), Err(Error::usage("Please provide the --factor value")));
+        Ok(())
+    }
+
+    struct Unfolding;
+
+    impl Solver for Unfolding {
+        fn day(&self) -> u8 { 12 }
+        fn part(&self) -> u8 { 2 }
+        fn title(&self) -> &str { "Hot Springs" }
+        fn solve(&self, input: String) -> Result<String, Error> { Ok(input) }
+        fn options(&self) -> &[SolverOption] { &[FACTOR] }
+    }
+
+    #[test]
+    fn solver_usage_with_options() {
+        let expected = "
+
+Day 12 part 2 options:
+    --factor <copies>              unfold the rows into this many copies
+                                   instead of 5";
+        assert_eq!(solver_usage(&Unfolding), format!("{}{}", ARGS_USAGE, expected));
+        assert_eq!(solver_usage(&crate::Puzzle::new(1, 1, "Trebuchet?!", Ok)), ARGS_USAGE);
+    }
+}
@@ -0,0 +1,97 @@
+use std::fmt;
+use crate::Error;
+
+/// An error together with the input it came from, for showing to humans.
+///
+/// Parse errors are rendered with the file path, the 1-based line and column,
+/// and the offending input line with the bad token underlined:
+///
+/// ```text
+/// error: Bad win number, invalid digit found in string
+///  --> day04/examples/ruksi.txt:3:12
+///   |
+/// 3 | Card 3: 41 4x 83 | 83 86
+///   |            ^^
+/// ```
+pub struct Diagnostic<'a> {
+    error: &'a Error,
+    path: &'a str,
+    input: &'a str,
+}
+
+impl<'a> Diagnostic<'a> {
+    pub fn new(error: &'a Error, path: &'a str, input: &'a str) -> Self {
+        Self { error, path, input }
+    }
+}
+
+impl fmt::Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Error::Parse { line, column, message } = self.error else {
+            return match self.error {
+                Error::Io(_) | Error::Usage(_) => write!(f, "error: {}", self.error),
+                _ => write!(f, "error: {}\n --> {}", self.error, self.path),
+            };
+        };
+
+        let gutter = " ".repeat(line.to_string().len());
+        write!(f, "error: {}\n{}--> {}:{}:{}", message, gutter, self.path, line, column)?;
+        let Some(text) = self.input.lines().nth(line.saturating_sub(1)) else {
+            return Ok(()); // the input has changed under us? 🤷
+        };
+
+        // underline the token starting at the column, up to the next whitespace
+        let offset = column.saturating_sub(1);
+        let width = text
+            .chars()
+            .skip(offset)
+            .take_while(|c| !c.is_whitespace())
+            .count()
+            .max(1);
+        write!(f, "\n{} |", gutter)?;
+        write!(f, "\n{} | {}", line, text)?;
+        write!(f, "\n{} | {}{}", gutter, " ".repeat(offset), "^".repeat(width))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rendering_parse_errors() {
+        let input = "Card 1: 41 48 | 83 86\nCard 2: 13 3x | 61 30";
+        let error = Error::parse(2, 12, "Bad win number");
+        let expected = "\
+error: Bad win number
+ --> cards.txt:2:12
+  |
+2 | Card 2: 13 3x | 61 30
+  |            ^^";
+        assert_eq!(Diagnostic::new(&error, "cards.txt", input).to_string(), expected);
+
+        let error = Error::parse(1, 22, "No more cards");
+        let rendered = Diagnostic::new(&error, "cards.txt", input).to_string();
+        assert!(rendered.ends_with("1 | Card 1: 41 48 | 83 86\n  |                      ^"));
+
+        // hand-made errors may count from zero, pointing at the first line and column
+        let error = Error::parse(0, 0, "Bad card");
+        let rendered = Diagnostic::new(&error, "cards.txt", input).to_string();
+        assert!(rendered.ends_with("0 | Card 1: 41 48 | 83 86\n  | ^^^^"));
+    }
+
+    #[test]
+    fn rendering_other_errors() {
+        let error = Error::no_solution("No path");
+        let rendered = Diagnostic::new(&error, "maze.txt", "").to_string();
+        assert_eq!(rendered, "error: No solution: No path\n --> maze.txt");
+
+        let error = Error::io("File does not exist");
+        let rendered = Diagnostic::new(&error, "maze.txt", "").to_string();
+        assert_eq!(rendered, "error: File does not exist");
+
+        let error = Error::usage("Unknown option: --nope");
+        let rendered = Diagnostic::new(&error, "maze.txt", "").to_string();
+        assert_eq!(rendered, "error: Unknown option: --nope");
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt;
+use std::io;
+
+/// The ways reading an input or solving a challenge can fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The input could not be read, e.g. the file does not exist.
+    Io(String),
+    /// The command line arguments are wrong, e.g. an unknown option.
+    Usage(String),
+    /// The input is malformed; the line and column are 1-based.
+    Parse { line: usize, column: usize, message: String },
+    /// The input was fine, but it has no answer, e.g. there is no path to the goal.
+    NoSolution(String),
+}
+
+impl Error {
+    pub fn io(message: impl Into<String>) -> Self {
+        Self::Io(message.into())
+    }
+
+    pub fn usage(message: impl Into<String>) -> Self {
+        Self::Usage(message.into())
+    }
+
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self::Parse { line, column, message: message.into() }
+    }
+
+    /// A parse error pointing at the start of `token`, which must be a slice of `text`.
+    pub fn parse_at(text: &str, token: &str, message: impl Into<String>) -> Self {
+        let (line, column) = locate(text, token);
+        Self::parse(line, column, message)
+    }
+
+    /// A parse error pointing just past the end of `text`, for when something is missing.
+    pub fn parse_at_end(text: &str, message: impl Into<String>) -> Self {
+        Self::parse_at(text, &text[text.len()..], message)
+    }
+
+    pub fn no_solution(message: impl Into<String>) -> Self {
+        Self::NoSolution(message.into())
+    }
+
+    /// Make a parse error of `inner`, a slice of `outer`, point to the same place in `outer`.
+    ///
+    /// This way parsers only need to know about the text they are given, and the callers
+    /// take care of placing the errors to the bigger picture, e.g. a line in the whole input.
+    pub fn within(self, outer: &str, inner: &str) -> Self {
+        let Self::Parse { line, column, message } = self else { return self; };
+        let (inner_line, inner_column) = locate(outer, inner);
+        match line {
+            1 => Self::parse(inner_line, inner_column + column - 1, message),
+            _ => Self::parse(inner_line + line - 1, column, message),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(message) | Error::Usage(message) => write!(f, "{}", message),
+            Error::Parse { line, column, message } => {
+                write!(f, "{} (line {}, column {})", message, line, column)
+            }
+            Error::NoSolution(message) => write!(f, "No solution: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e.to_string())
+    }
+}
+
+// so the `-> Result<(), String>` binaries and tests can keep using `?` on our errors
+impl From<Error> for String {
+    fn from(e: Error) -> Self {
+        e.to_string()
+    }
+}
+
+/// Find the 1-based (line, column) where `token`, a slice of `text`, starts.
+///
+/// Columns are counted in characters, not bytes. Falls back to (1, 1) if `token`
+/// doesn't actually lie inside `text`.
+pub fn locate(text: &str, token: &str) -> (usize, usize) {
+    let offset = (token.as_ptr() as usize)
+        .checked_sub(text.as_ptr() as usize)
+        .filter(|offset| offset.checked_add(token.len()).is_some_and(|end| end <= text.len()))
+        .filter(|offset| text.is_char_boundary(*offset));
+    let Some(offset) = offset else {
+        return (1, 1);
+    };
+    let before = &text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locating_tokens() {
+        let text = "seeds: 1 2\n\nalpha-to-beta\n50 98 2";
+        assert_eq!(locate(text, &text[0..5]), (1, 1));
+        assert_eq!(locate(text, &text[9..10]), (1, 10));
+        assert_eq!(locate(text, &text[12..17]), (3, 1));
+        assert_eq!(locate(text, &text[29..31]), (4, 4));
+        assert_eq!(locate(text, &text[text.len()..]), (4, 8));
+        assert_eq!(locate(text, "not a slice of the text"), (1, 1));
+        assert_eq!(locate(&text[12..], &text[0..5]), (1, 1)); // before the text
+        assert_eq!(locate(&text[..5], &text[3..8]), (1, 1)); // past the end of the text
+        assert_eq!(locate("äö x", &"äö x"[5..]), (1, 4));
+    }
+
+    #[test]
+    fn placing_parse_errors_within_outer_text() {
+        let text = "first line\nsecond line\n  third line";
+        let third = text.lines().nth(2).unwrap_or_default();
+        let error = Error::parse_at(third, &third[2..], "bad third").within(text, third);
+        assert_eq!(error, Error::parse(3, 3, "bad third"));
+
+        let block = &text[11..];
+        let error = Error::parse(2, 5, "bad block").within(text, block);
+        assert_eq!(error, Error::parse(3, 5, "bad block"));
+
+        let error = Error::no_solution("nope").within(text, block);
+        assert_eq!(error, Error::no_solution("nope"));
+    }
+
+    #[test]
+    fn displaying_errors() {
+        assert_eq!(Error::io("File does not exist").to_string(), "File does not exist");
+        assert_eq!(Error::usage("Unknown option: --nope").to_string(), "Unknown option: --nope");
+        assert_eq!(Error::parse(2, 7, "Bad number").to_string(), "Bad number (line 2, column 7)");
+        assert_eq!(Error::no_solution("No path").to_string(), "No solution: No path");
+    }
+}
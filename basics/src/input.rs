@@ -0,0 +1,170 @@
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use crate::{Args, Error};
+
+/// The input path meaning "read from stdin instead of a file".
+pub const STDIN_PATH: &str = "-";
+
+/// How to clean up the input contents before handing them to the solvers.
+///
+/// The default smooths over the usual differences between editors and operating systems,
+/// e.g. CRLF files from Windows, so solvers can split on `"\n\n"` without a worry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Normalization {
+    /// Remove the byte order mark from the start of the input.
+    pub bom: bool,
+    /// Convert CRLF line endings to LF.
+    pub crlf: bool,
+    /// Strip trailing whitespace from the end of each line.
+    pub trailing_whitespace: bool,
+    /// Remove the trailing newline(s) from the end of the input.
+    pub trailing_newlines: bool,
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Self { bom: true, crlf: true, trailing_whitespace: false, trailing_newlines: true }
+    }
+}
+
+impl Normalization {
+    /// Keep the input contents exactly as they are.
+    pub fn none() -> Self {
+        Self { bom: false, crlf: false, trailing_whitespace: false, trailing_newlines: false }
+    }
+
+    pub fn apply(&self, input: String) -> String {
+        let mut input = input;
+        if self.bom {
+            if let Some(rest) = input.strip_prefix('\u{feff}') {
+                input = rest.to_string();
+            }
+        }
+        if self.crlf && input.contains('\r') {
+            input = input.replace("\r\n", "\n");
+        }
+        if self.trailing_whitespace {
+            input = input.split('\n').map(str::trim_end).collect::<Vec<_>>().join("\n");
+        }
+        if self.trailing_newlines && input.ends_with('\n') {
+            input = input.trim_end_matches('\n').to_string();
+        }
+        input
+    }
+}
+
+/// Read input contents from the first file specified in the command line arguments.
+pub fn read_input() -> Result<String, Error> {
+    read_first_input(&Args::from_env()?)
+}
+
+// e.g. with just `--users` there's no input path to read
+fn read_first_input(args: &Args) -> Result<String, Error> {
+    let path = args.input_paths.first().ok_or_else(|| Error::usage("No input path given"))?;
+    read_input_file_with(path, args.normalization)
+}
+
+/// Read input contents from all the files specified in the command line arguments,
+/// as `(path, contents)` pairs in the order they were given.
+pub fn read_inputs() -> Result<Vec<(String, String)>, Error> {
+    let args = Args::from_env()?;
+    args.input_paths
+        .into_iter()
+        .map(|path| read_input_file_with(&path, args.normalization).map(|input| (path, input)))
+        .collect()
+}
+
+/// Read and normalize input contents from the given file, relative to the working directory.
+///
+/// The path `-` reads from stdin instead.
+pub fn read_input_file(path: impl AsRef<Path>) -> Result<String, Error> {
+    read_input_file_with(path, Normalization::default())
+}
+
+pub fn read_input_file_with(path: impl AsRef<Path>, normalization: Normalization) -> Result<String, Error> {
+    if path.as_ref() == Path::new(STDIN_PATH) {
+        return read_input_from_with(io::stdin().lock(), normalization);
+    }
+    let input_path = validate_input_path(path)?;
+    let file = fs::File::open(input_path)?;
+    read_input_from_with(file, normalization)
+}
+
+/// Read and normalize input contents from anything readable, e.g. stdin, a pipe
+/// or a byte slice in tests.
+pub fn read_input_from(reader: impl Read) -> Result<String, Error> {
+    read_input_from_with(reader, Normalization::default())
+}
+
+pub fn read_input_from_with(mut reader: impl Read, normalization: Normalization) -> Result<String, Error> {
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    Ok(normalization.apply(input))
+}
+
+/// How to refer to the input path in messages, e.g. `<stdin>` for `-`.
+pub fn input_name(path: &str) -> &str {
+    if path == STDIN_PATH { "<stdin>" } else { path }
+}
+
+/// Resolve the path against the working directory and make sure it is a readable file.
+pub fn validate_input_path(path: impl AsRef<Path>) -> Result<PathBuf, Error> {
+    let working_directory = env::current_dir()?;
+    let absolute = working_directory.join(path);
+    if !absolute.exists() {
+        return Err(Error::io(format!("File does not exist: {}", absolute.display())));
+    }
+    if !absolute.is_file() {
+        return Err(Error::io(format!("Path is not a file: {}", absolute.display())));
+    }
+    fs::File::options()
+        .read(true)
+        .open(absolute.clone())
+        .map_err(|_e| Error::io(format!("File is not readable: {}", absolute.display())))?; // probably 🤷
+    Ok(absolute)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reading_input_from_readers() -> Result<(), String> {
+        assert_eq!(read_input_from("a\nb\n\n".as_bytes())?, "a\nb");
+        assert_eq!(read_input_from("a\n\nb".as_bytes())?, "a\n\nb");
+        assert_eq!(read_input_from("".as_bytes())?, "");
+        Ok(())
+    }
+
+    #[test]
+    fn reading_input_files() {
+        assert!(read_input_file("Cargo.toml").is_ok_and(|input| input.starts_with("[package]")));
+        assert!(read_input_file("does-not-exist.txt").is_err());
+        assert!(read_input_file("src").is_err());
+    }
+
+    #[test]
+    fn reading_the_first_input() -> Result<(), String> {
+        let args = Args::parse(["Cargo.toml", "does-not-exist.txt"])?;
+        assert!(read_first_input(&args).is_ok_and(|input| input.starts_with("[package]")));
+        let args = Args::parse(["--users"])?;
+        assert_eq!(read_first_input(&args), Err(Error::usage("No input path given")));
+        Ok(())
+    }
+
+    #[test]
+    fn normalizing_inputs() -> Result<(), String> {
+        let windows = "\u{feff}seeds: 1 2 \r\n\r\nalpha-to-beta:\t\r\n50 98 2\r\n";
+        assert_eq!(read_input_from(windows.as_bytes())?, "seeds: 1 2 \n\nalpha-to-beta:\t\n50 98 2");
+
+        let stripping = Normalization { trailing_whitespace: true, ..Normalization::default() };
+        let input = read_input_from_with(windows.as_bytes(), stripping)?;
+        assert_eq!(input, "seeds: 1 2\n\nalpha-to-beta:\n50 98 2");
+
+        let input = read_input_from_with(windows.as_bytes(), Normalization::none())?;
+        assert_eq!(input, windows);
+        Ok(())
+    }
+}
@@ -1,35 +1,155 @@
 use std::env;
-use std::fs;
-use std::path::PathBuf;
-
-/// Read input contents from the file specified in the command line arguments.
-pub fn read_input() -> Result<String, String> {
-    let input_path = get_input_path()?;
-    let input = fs::read_to_string(input_path).map_err(|e| e.to_string())?;
-    // remove trailing newline(s) if present to get consistent input content
-    if input.ends_with('\n') {
-        return Ok(input.trim_end_matches('\n').to_string());
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::ExitCode;
+
+mod answers;
+mod args;
+mod diagnostic;
+mod error;
+mod input;
+mod solver;
+mod timing;
+
+pub use answers::{
+    check_answers, day_dir, orphan_sidecars, sidecar_answers, user_inputs, verify, Answers, Expected, Verdict,
+    ANSWERS_FILE, SIDECAR_EXTENSION,
+};
+pub use args::{solver_usage, Args, ARGS_USAGE};
+pub use diagnostic::Diagnostic;
+pub use error::{locate, Error};
+pub use input::{
+    input_name, read_input, read_input_file, read_input_file_with, read_input_from,
+    read_input_from_with, read_inputs, validate_input_path, Normalization, STDIN_PATH,
+};
+pub use solver::{Puzzle, Registry, SolveFn, Solver, SolverOption};
+pub use timing::{parsing, timed, Bench, Timing};
+
+/// Run the solver on each input file specified in the command line arguments, in sequence.
+///
+/// Prints the results, or a diagnostic pointing at the problem in the input file.
+/// With more than one input, or with `--users`, each result is prefixed with the path it came from.
+/// With `--time` or `--bench <runs>`, the timings are reported to stderr.
+/// With `--users`, all the user inputs of the day are solved too, and the results of inputs
+/// with a sidecar answers file are checked against it, reporting the disagreeing ones.
+/// The extra options of the solver, if it takes any, run a variation of the part instead.
+pub fn run(solver: impl Solver) -> ExitCode {
+    let configured = Args::parse_with(env::args().skip(1), solver.options())
+        .and_then(|args| configure(&solver, &args).map(|configured| (args, configured)));
+    let (args, configured) = match configured {
+        Ok(configured) => configured,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, solver_usage(&solver));
+            return ExitCode::from(2);
+        }
+    };
+    match solve_inputs(configured.as_deref().unwrap_or(&solver), &args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Like `run`, but with already parsed arguments, for tooling like the `aoc` runner.
+///
+/// The diagnostics are printed as they come; the error only tells how many inputs failed,
+/// or which of the extra options of the solver are wrong.
+pub fn run_with(solver: &dyn Solver, args: &Args) -> Result<(), String> {
+    let configured = configure(solver, args)?;
+    solve_inputs(configured.as_deref().unwrap_or(solver), args)
+}
+
+// the variation of the part with the extra options, if any were given
+fn configure(solver: &dyn Solver, args: &Args) -> Result<Option<Box<dyn Solver>>, Error> {
+    match args.options.is_empty() {
+        true => Ok(None),
+        false => solver.with_options(&args.options).map(Some),
     }
-    Ok(input)
 }
 
-/// Get and validate the input path from the command line arguments.
-pub fn get_input_path() -> Result<PathBuf, String> {
-    // iterators must be mutable to be iterated over
-    let mut args = env::args();
-    let _binary_path_we_dont_need = args.next();
-    let relative = args.next().ok_or("Please provide input file path")?;
-    let working_directory = env::current_dir().map_err(|e| e.to_string())?;
-    let absolute = working_directory.join(relative);
-    if !absolute.exists() {
-        return Err(format!("File does not exist: {}", absolute.display()));
+fn solve_inputs(solver: &dyn Solver, args: &Args) -> Result<(), String> {
+    let label = format!("day{:02} part{}", solver.day(), solver.part());
+    let mut input_paths = args.input_paths.clone();
+    if args.users {
+        let user_inputs = user_inputs(&day_dir(solver.day()));
+        input_paths.extend(user_inputs.iter().map(|path| path.to_string_lossy().to_string()));
     }
-    if !absolute.is_file() {
-        return Err(format!("Path is not a file: {}", absolute.display()));
+    let mut failures = 0;
+    let mut disagreements = vec![];
+    for path in input_paths.iter() {
+        let shown_path = input_name(path);
+        let input = match read_input_file_with(path, args.normalization) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("{}", Diagnostic::new(&e, shown_path, ""));
+                failures += 1;
+                continue;
+            }
+        };
+
+        let (result, timing) = solve_timed(solver, &input);
+        let result = match result {
+            Ok(result) => result,
+            Err(e) => {
+                eprintln!("{}", Diagnostic::new(&e, shown_path, &input));
+                failures += 1;
+                continue;
+            }
+        };
+        let shown_result = match input_paths.len() == 1 && !args.users {
+            true => result.clone(),
+            false => format!("{}: {}", shown_path, result),
+        };
+        // stop quietly when the reader has seen enough, e.g. a long listing piped to head
+        if writeln!(io::stdout().lock(), "{}", shown_result).is_err() {
+            return Ok(());
+        }
+
+        let expected = match solver.checks_answers() {
+            true => sidecar_answers(Path::new(path)),
+            false => Ok(None),
+        };
+        match expected {
+            Ok(Some(expected)) => match expected.part(solver.part()) {
+                Some(answer) if answer != result => {
+                    let verdict = Verdict::Fail { expected: answer.to_string(), actual: result };
+                    eprintln!("{} {}: {}", label, shown_path, verdict);
+                    disagreements.push(shown_path);
+                }
+                _ => {}
+            },
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!("error: {}", e);
+                failures += 1;
+            }
+        }
+
+        if let Some(runs) = args.bench {
+            let mut timings = vec![timing];
+            timings.extend((1..runs).map(|_| solve_timed(solver, &input).1));
+            eprintln!("{} {}: {}", label, shown_path, Bench::new(timings));
+        } else if args.time {
+            eprintln!("{} {}: {}", label, shown_path, timing);
+        }
+    }
+    let mut problems = vec![];
+    if failures > 0 {
+        problems.push(format!("{} of {} input(s) failed", failures, input_paths.len()));
     }
-    fs::File::options()
-        .read(true)
-        .open(absolute.clone())
-        .map_err(|_e| format!("File is not readable: {}", absolute.display()))?; // probably 🤷
-    Ok(absolute)
+    if !disagreements.is_empty() {
+        let count = disagreements.len();
+        problems.push(format!("{} input(s) disagree with their answers: {}", count, disagreements.join(", ")));
+    }
+    match problems.is_empty() {
+        true => Ok(()),
+        false => Err(problems.join(", ")),
+    }
+}
+
+fn solve_timed(solver: &dyn Solver, input: &str) -> (Result<String, Error>, Timing) {
+    let input = input.to_string(); // copy outside of the timing
+    timed(|| solver.solve(input))
 }
@@ -0,0 +1,147 @@
+use crate::Error;
+
+/// The signature shared by all the `solve_dayXX_partN` functions.
+pub type SolveFn = fn(String) -> Result<String, Error>;
+
+/// A solver for one part of a day's challenge.
+pub trait Solver {
+    /// The day of the challenge, e.g. `7` for "Day 7: Camel Cards".
+    fn day(&self) -> u8;
+
+    /// The part of the challenge, either `1` or `2`.
+    fn part(&self) -> u8;
+
+    /// The challenge title, e.g. "Camel Cards".
+    fn title(&self) -> &str;
+
+    /// Solve the challenge for the given input contents.
+    fn solve(&self, input: String) -> Result<String, Error>;
+
+    /// Whether the answers recorded for the part apply, i.e. not for a variation of the part.
+    fn checks_answers(&self) -> bool {
+        true
+    }
+
+    /// The extra options the solver takes for variations of the part, none by default.
+    fn options(&self) -> &[SolverOption] {
+        &[]
+    }
+
+    /// The variation of the part with the extra options given, as `(name, value)` pairs
+    /// in the order they were given, all of them among the `options`.
+    fn with_options(&self, options: &[(String, String)]) -> Result<Box<dyn Solver>, Error> {
+        let names = options.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>();
+        Err(Error::usage(format!("Day {} part {} takes no options: {}", self.day(), self.part(), names.join(", "))))
+    }
+}
+
+/// An extra `--<name> <value>` option of a solver, e.g. the unfolding factor of day 12 part 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolverOption {
+    /// The option itself, e.g. `--factor`.
+    pub name: &'static str,
+    /// What the value is, e.g. `<copies>`.
+    pub value: &'static str,
+    /// What the option does, possibly on several lines.
+    pub help: &'static str,
+}
+
+/// A solver backed by one of the plain `solve_dayXX_partN` functions.
+#[derive(Clone, Copy)]
+pub struct Puzzle {
+    day: u8,
+    part: u8,
+    title: &'static str,
+    solve: SolveFn,
+}
+
+impl Puzzle {
+    pub const fn new(day: u8, part: u8, title: &'static str, solve: SolveFn) -> Self {
+        Self { day, part, title, solve }
+    }
+}
+
+impl Solver for Puzzle {
+    fn day(&self) -> u8 { self.day }
+    fn part(&self) -> u8 { self.part }
+    fn title(&self) -> &str { self.title }
+    fn solve(&self, input: String) -> Result<String, Error> { (self.solve)(input) }
+}
+
+/// A collection of solvers that tooling can enumerate instead of hard-coding solver names.
+#[derive(Default)]
+pub struct Registry {
+    solvers: Vec<Box<dyn Solver>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a solver, replacing any earlier solver registered for the same day and part.
+    pub fn register(&mut self, solver: impl Solver + 'static) {
+        self.solvers.retain(|s| s.day() != solver.day() || s.part() != solver.part());
+        self.solvers.push(Box::new(solver));
+        self.solvers.sort_by_key(|s| (s.day(), s.part()));
+    }
+
+    /// Find the solver for the given day and part, if one has been registered.
+    pub fn find(&self, day: u8, part: u8) -> Option<&dyn Solver> {
+        self.iter().find(|s| s.day() == day && s.part() == part)
+    }
+
+    /// Iterate over all the registered solvers, ordered by day and part.
+    pub fn iter(&self) -> impl Iterator<Item=&dyn Solver> {
+        self.solvers.iter().map(|s| s.as_ref())
+    }
+
+    pub fn len(&self) -> usize {
+        self.solvers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.solvers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn echo(input: String) -> Result<String, Error> {
+        Ok(input)
+    }
+
+    fn shout(input: String) -> Result<String, Error> {
+        Ok(input.to_uppercase())
+    }
+
+    #[test]
+    fn registering_and_finding_solvers() -> Result<(), String> {
+        let mut registry = Registry::new();
+        registry.register(Puzzle::new(2, 1, "Second", echo));
+        registry.register(Puzzle::new(1, 2, "First", shout));
+        registry.register(Puzzle::new(1, 1, "First", echo));
+        assert_eq!(registry.len(), 3);
+
+        let order = registry.iter().map(|s| (s.day(), s.part())).collect::<Vec<_>>();
+        assert_eq!(order, vec![(1, 1), (1, 2), (2, 1)]);
+
+        let solver = registry.find(1, 2).ok_or("no solver found")?;
+        assert_eq!(solver.title(), "First");
+        assert_eq!(solver.solve("hey".to_string())?, "HEY");
+        assert!(registry.find(3, 1).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn registering_replaces_the_same_day_and_part() -> Result<(), String> {
+        let mut registry = Registry::new();
+        registry.register(Puzzle::new(1, 1, "Old", echo));
+        registry.register(Puzzle::new(1, 1, "New", shout));
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry.find(1, 1).ok_or("no solver found")?.title(), "New");
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+use std::cell::Cell;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    // parse time accumulated by `parsing` since the last `timed` started
+    static PARSE_TIME: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+}
+
+/// Mark a part of a solver as parsing the input, so timing can tell it apart from solving.
+///
+/// Costs next to nothing when nobody is timing, so solvers can wrap their parsing freely.
+pub fn parsing<T>(parse: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let parsed = parse();
+    let elapsed = start.elapsed();
+    PARSE_TIME.with(|time| time.set(time.get() + elapsed));
+    parsed
+}
+
+/// How long one run of a solver took, split to parsing and solving.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Timing {
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Timing {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+impl fmt::Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "parse {:.2?}, solve {:.2?}, total {:.2?}", self.parse, self.solve, self.total())
+    }
+}
+
+/// Run `f`, e.g. a solver, and time it; the parts wrapped in `parsing` count as parse time.
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Timing) {
+    PARSE_TIME.with(|time| time.set(Duration::ZERO));
+    let start = Instant::now();
+    let result = f();
+    let total = start.elapsed();
+    let parse = PARSE_TIME.with(|time| time.replace(Duration::ZERO)).min(total);
+    (result, Timing { parse, solve: total - parse })
+}
+
+/// The timings of running the same solver repeatedly on the same input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bench(Vec<Timing>);
+
+impl Bench {
+    pub fn new(timings: Vec<Timing>) -> Self {
+        let mut timings = timings;
+        timings.sort_by_key(Timing::total);
+        Self(timings)
+    }
+
+    pub fn runs(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn min(&self) -> Timing {
+        self.0.first().copied().unwrap_or_default()
+    }
+
+    pub fn median(&self) -> Timing {
+        self.0.get(self.0.len() / 2).copied().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> Timing {
+        self.0.last().copied().unwrap_or_default()
+    }
+}
+
+impl fmt::Display for Bench {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (min, median, max) = (self.min(), self.median(), self.max());
+        write!(
+            f,
+            "{} runs, min {:.2?}, median {:.2?}, max {:.2?} (median parse {:.2?}, solve {:.2?})",
+            self.runs(), min.total(), median.total(), max.total(), median.parse, median.solve,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use super::*;
+
+    #[test]
+    fn timing_parsing_apart_from_solving() {
+        let (result, timing) = timed(|| {
+            let parsed = parsing(|| {
+                thread::sleep(Duration::from_millis(20));
+                21
+            });
+            thread::sleep(Duration::from_millis(10));
+            parsed * 2
+        });
+        assert_eq!(result, 42);
+        assert!(timing.parse >= Duration::from_millis(20));
+        assert!(timing.solve >= Duration::from_millis(10));
+
+        // the parse time doesn't leak to the next run
+        let ((), timing) = timed(|| ());
+        assert_eq!(timing.parse, Duration::ZERO);
+    }
+
+    #[test]
+    fn benching_statistics() {
+        let ms = Duration::from_millis;
+        let bench = Bench::new(vec![
+            Timing { parse: ms(1), solve: ms(5) },
+            Timing { parse: ms(1), solve: ms(1) },
+            Timing { parse: ms(2), solve: ms(2) },
+        ]);
+        assert_eq!(bench.runs(), 3);
+        assert_eq!(bench.min().total(), ms(2));
+        assert_eq!(bench.median().total(), ms(4));
+        assert_eq!(bench.max().total(), ms(6));
+    }
+}
@@ -1,6 +1,5 @@
-fn main() -> Result<(), String> {
-    let input = basics::read_input()?;
-    let result = day00::solve_day00_part2(input)?;
-    println!("{}", result);
-    Ok(())
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    basics::run(day00::PART2)
 }
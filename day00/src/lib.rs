@@ -1,41 +1,34 @@
-pub fn solve_day00_part1(input: String) -> Result<String, String> {
+use basics::{Error, Puzzle, Registry};
+
+pub const PART1: Puzzle = Puzzle::new(0, 1, TITLE, solve_day00_part1);
+pub const PART2: Puzzle = Puzzle::new(0, 2, TITLE, solve_day00_part2);
+const TITLE: &str = "Day before the Days";
+
+/// Register the solvers of the day for tooling like the `aoc` runner.
+pub fn register(registry: &mut Registry) {
+    registry.register(PART1);
+    registry.register(PART2);
+}
+
+pub fn solve_day00_part1(input: String) -> Result<String, Error> {
     Ok(input)
 }
 
-pub fn solve_day00_part2(input: String) -> Result<String, String> {
+pub fn solve_day00_part2(input: String) -> Result<String, Error> {
     Ok(input)
 }
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
     use super::*;
 
     #[test]
-    fn solve_day00_part1_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day00_part1(input)?, "#yolo");
-        Ok(())
+    fn solve_day00_part1_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART1)
     }
 
-    // #[test]
-    // fn solve_day00_part1_on_my_input() -> Result<(), String> {
-    //     let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-    //     assert_eq!(solve_day00_part1(input)?, "#yolo");
-    //     Ok(())
-    // }
-
-    // #[test]
-    // fn solve_day00_part2_on_example() -> Result<(), String> {
-    //     let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-    //     assert_eq!(solve_day00_part2(input)?, "#yolo");
-    //     Ok(())
-    // }
-
-    // #[test]
-    // fn solve_day00_part2_on_my_input() -> Result<(), String> {
-    //     let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-    //     assert_eq!(solve_day00_part2(input)?, "#yolo");
-    //     Ok(())
-    // }
+    #[test]
+    fn solve_day00_part2_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART2)
+    }
 }
@@ -1,6 +1,6 @@
-fn main() -> Result<(), String> {
-    let input = basics::read_input()?;
-    let result = day01::solve_day01_part2(input)?;
-    println!("{}", result);
-    Ok(())
+use std::process::ExitCode;
+use day01::{Calibrating, Vocabulary};
+
+fn main() -> ExitCode {
+    basics::run(Calibrating::new(Vocabulary::english()))
 }
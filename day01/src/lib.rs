@@ -1,128 +1,221 @@
-use std::collections::HashMap;
-use regex::{Captures, Regex};
-
-pub fn solve_day01_part1(input: String) -> Result<String, String> {
-    let result: u32 = input
-        .split("\n")
-        .map(|line| {
-            let Some(left) = line.chars().find(|c| c.to_digit(10).is_some()) else {
-                panic!("No digit found on line: {}", line)
-            };
-            let right = line
-                .chars()
-                .rfind(|c| c.to_digit(10).is_some())
-                .expect("but we found one searching from the left!?");
-            let as_string = vec![left, right].into_iter().collect::<String>();
-            return as_string.parse::<u32>().expect("but separate characters were digits!?");
-        })
-        .sum();
-    Ok(result.to_string())
+use std::cmp::Reverse;
+use basics::{parsing, Error, Puzzle, Registry, Solver, SolverOption};
+
+pub use report::{calibration_report, Found, LineReport, Report};
+pub use scanner::{Match, Scanner};
+pub use vocabulary::Vocabulary;
+
+mod report;
+mod scanner;
+mod vocabulary;
+
+pub const PART1: Puzzle = Puzzle::new(1, 1, TITLE, solve_day01_part1);
+pub const PART2: Puzzle = Puzzle::new(1, 2, TITLE, solve_day01_part2);
+const TITLE: &str = "Trebuchet?!";
+
+/// Register the solvers of the day for tooling like the `aoc` runner.
+pub fn register(registry: &mut Registry) {
+    registry.register(PART1);
+    registry.register(Calibrating::new(Vocabulary::english()));
+}
+
+pub fn solve_day01_part1(input: String) -> Result<String, Error> {
+    calibration_sum(&input, &Vocabulary::digits())
+}
+
+pub fn solve_day01_part2(input: String) -> Result<String, Error> {
+    calibration_sum(&input, &Vocabulary::english())
+}
+
+/// The part 2 solver with the digits spelled in another vocabulary, e.g. for localized inputs
+/// with `--vocabulary finnish`, or auditing how each line was calibrated with `--report`.
+#[derive(Debug, Clone)]
+pub struct Calibrating {
+    vocabulary: Vocabulary,
+    report: Option<ReportFormat>,
 }
 
-pub fn solve_day01_part2(input: String) -> Result<String, String> {
-    let replacements: HashMap<&str, &str> = HashMap::from([
-        ("zero", "0"),
-        ("one", "1"),
-        ("two", "2"),
-        ("three", "3"),
-        ("four", "4"),
-        ("five", "5"),
-        ("six", "6"),
-        ("seven", "7"),
-        ("eight", "8"),
-        ("nine", "9"),
-    ]);
-
-    // go through each line, potentially replacing _the first_ word with its digit
-    let word_group = replacements
-        .keys()
-        .map(|&s| s.to_string())
-        .collect::<Vec<String>>()
-        .join("|");
-    let re_from_left = Regex::new(word_group.as_str()).map_err(|e| e.to_string())?;
-    let lefts = input
-        .split("\n")
-        .map(|line| {
-            re_from_left.replace(line, |caps: &Captures| {
-                format!("{}", replacements
-                    .get(&caps[0])
-                    .expect("but the match group was made from the replacement keys!?")
-                )
-            }).to_string()
-        })
-        .collect::<Vec<String>>();
-
-    // go through each line, potentially replacing _the last_ word with its digit
-    // this is wanted because a line can have something like "eightwo" which could be
-    // replaced with "8wo" or "eight2" depending on from which end we start searching
-    // as we only want the last match, we reverse both the needle (word) and the haystack (line)
-    let reversed_word_group = word_group.chars().rev().collect::<String>();  // word flip ↩️
-    let re_from_right = Regex::new(reversed_word_group.as_str()).map_err(|e| e.to_string())?;
-    let rights = input
-        .split("\n")
-        .map(|line| {
-            let line = line.chars().rev().collect::<String>(); // line flip ↩️
-            re_from_right.replace(
-                &line,
-                |caps: &Captures| {
-                    let lookup_key = &caps[0].chars().rev().collect::<String>(); // word flop ↪️
-                    format!("{}", replacements
-                        .get(lookup_key.as_str())
-                        .expect("but the match group was made from the replacement keys!?")
-                    )
-                },
-            ).chars().rev().collect::<String>() // line flop ↪️
-        })
-        .collect::<Vec<String>>();
-
-    // zip-combine the two lists, so we can read
-    // the first digit from the lefts
-    // and the last digit from the rights
-    let zipped = lefts.iter().zip(rights.iter());
-    let result: u32 = zipped.map(|(left, right)| {
-        let Some(left) = left.chars().find(|c| c.to_digit(10).is_some()) else {
-            panic!("No digit found on left-digit-ed line: {}", left)
+/// How `--report` shows the calibration of each line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat { Table, Json }
+
+impl Calibrating {
+    pub fn new(vocabulary: Vocabulary) -> Self {
+        Self { vocabulary, report: None }
+    }
+
+    /// Report the calibration of each line instead of just the sum.
+    pub fn reporting(self, format: ReportFormat) -> Self {
+        Self { report: Some(format), ..self }
+    }
+}
+
+impl Solver for Calibrating {
+    fn day(&self) -> u8 { 1 }
+    fn part(&self) -> u8 { 2 }
+    fn title(&self) -> &str { TITLE }
+
+    fn solve(&self, input: String) -> Result<String, Error> {
+        let Some(format) = self.report else {
+            return calibration_sum(&input, &self.vocabulary);
         };
-        let Some(right) = right.chars().rfind(|c| c.to_digit(10).is_some()) else {
-            panic!("No digit found on right-digit-ed line: {}", right)
+        let report = calibration_report(&input, &self.vocabulary);
+        let output = match format {
+            ReportFormat::Table => report.to_table(),
+            ReportFormat::Json => report.to_json(),
         };
-        let as_string = vec![left, right].into_iter().collect::<String>();
-        return as_string.parse::<u32>().expect("but separate characters were digits!?");
-    }).sum();
+        Ok(output.trim_end().to_string())
+    }
+
+    fn checks_answers(&self) -> bool { self.vocabulary == Vocabulary::english() && self.report.is_none() }
+    fn options(&self) -> &[SolverOption] { &[VOCABULARY_OPTION, REPORT_OPTION] }
 
-    return Ok(result.to_string());
+    fn with_options(&self, options: &[(String, String)]) -> Result<Box<dyn Solver>, Error> {
+        let mut calibrating = self.clone();
+        for (option, value) in options {
+            match option.as_str() {
+                "--report" => {
+                    let format = match value.as_str() {
+                        "table" => ReportFormat::Table,
+                        "json" => ReportFormat::Json,
+                        _ => return Err(Error::usage(format!("Invalid --report format: {}", value))),
+                    };
+                    calibrating = calibrating.reporting(format);
+                }
+                _ => {
+                    calibrating.vocabulary = Vocabulary::from_argument(value)
+                        .map_err(|e| Error::usage(format!("Invalid --vocabulary {}, {}", value, e)))?;
+                }
+            }
+        }
+        Ok(Box::new(calibrating))
+    }
+}
+
+const VOCABULARY_OPTION: SolverOption = SolverOption {
+    name: "--vocabulary",
+    value: "<name | file>",
+    help: "spell the digits in english (the default), finnish, german\n\
+           or roman, not at all with digits, or with the `<word> <digit>`\n\
+           lines of the file, not checking the answers when not in english",
+};
+
+const REPORT_OPTION: SolverOption = SolverOption {
+    name: "--report",
+    value: "<table | json>",
+    help: "report the first and the last digit of each line for auditing,\n\
+           the words they were spelled with and their byte positions,\n\
+           the lines without digits included as errors",
+};
+
+fn calibration_sum(input: &str, vocabulary: &Vocabulary) -> Result<String, Error> {
+    let lines = parsing(|| input.split('\n').collect::<Vec<_>>());
+    let scanner = Scanner::new(vocabulary);
+    let result = lines
+        .into_iter()
+        .map(|line| calibration_value(&scanner, line).ok_or_else(|| Error::parse_at(input, line, no_digit(vocabulary))))
+        .sum::<Result<u32, Error>>()?;
+    Ok(result.to_string())
+}
+
+fn no_digit(vocabulary: &Vocabulary) -> &'static str {
+    match vocabulary.has_spelled_words() {
+        true => "No digit or digit word found on line",
+        false => "No digit found on line",
+    }
+}
+
+/// The first and the last digit on the line as a two-digit number, the spelled digits included.
+fn calibration_value(scanner: &Scanner, line: &str) -> Option<u32> {
+    let (first, last) = first_and_last(scanner, line)?;
+    Some(first.digit * 10 + last.digit)
+}
+
+/// The first and the last digit on the line, the same one when there's only one.
+///
+/// The words may overlap, like in `eightwo`, which starts with 8 and ends with 2;
+/// of the words starting or ending together, like `vii` and `viii`, the longest counts.
+fn first_and_last(scanner: &Scanner, line: &str) -> Option<(Match, Match)> {
+    let matches = scanner.find_all(line);
+    let first = matches.iter().min_by_key(|m| (m.start, Reverse(m.end)))?;
+    let last = matches.iter().max_by_key(|m| (m.end, Reverse(m.start)))?;
+    Some((*first, *last))
 }
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
     use super::*;
 
     #[test]
-    fn solve_day01_part1_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/part1-example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day01_part1(input)?, "142");
-        Ok(())
+    fn lines_without_digits_are_errors() {
+        let input = "1abc2\nnothing here".to_string();
+        let error = Error::parse(2, 1, "No digit found on line");
+        assert_eq!(solve_day01_part1(input), Err(error));
+        let input = "two1nine\nnothing here".to_string();
+        let error = Error::parse(2, 1, "No digit or digit word found on line");
+        assert_eq!(solve_day01_part2(input), Err(error));
     }
 
     #[test]
-    fn solve_day01_part1_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day01_part1(input)?, "56397");
-        Ok(())
+    fn overlapping_words_count_from_both_ends() {
+        let scanner = Scanner::new(&Vocabulary::english());
+        assert_eq!(calibration_value(&scanner, "eightwo"), Some(82));
+        assert_eq!(calibration_value(&scanner, "xtwone3four"), Some(24));
+        assert_eq!(calibration_value(&scanner, "7pqrstsixteen"), Some(76));
+        assert_eq!(calibration_value(&scanner, "oneight"), Some(18));
+        assert_eq!(calibration_value(&scanner, "zero5"), Some(55));
+        assert_eq!(calibration_value(&scanner, "zero"), None);
     }
 
     #[test]
-    fn solve_day01_part2_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/part2-example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day01_part2(input)?, "281");
+    fn calibrating_in_other_languages() -> Result<(), String> {
+        let input = "kaksikolmenelja\nyhdeksänkahdeksan\n4kuusitoista".to_string();
+        assert_eq!(Calibrating::new(Vocabulary::finnish()).solve(input)?, (23 + 98 + 46).to_string());
+        let input = "achtzweiundzwanzig\nfünfzehn\n7sechs".to_string();
+        assert_eq!(Calibrating::new(Vocabulary::german()).solve(input)?, (82 + 55 + 76).to_string());
+        let input = "xviiix\nmcmxciv\nlxvi".to_string();
+        assert_eq!(Calibrating::new(Vocabulary::roman()).solve(input)?, (89 + 44 + 66).to_string());
+
+        let input = basics::read_input_file("examples/part2-example.txt")?;
+        assert_eq!(Calibrating::new(Vocabulary::english()).solve(input.clone())?, solve_day01_part2(input)?);
+        assert!(!Calibrating::new(Vocabulary::roman()).checks_answers());
+        let options = [("--vocabulary".to_string(), "roman".to_string())];
+        assert_eq!(Calibrating::new(Vocabulary::english()).with_options(&options)?.solve("xviiix".to_string())?, "89");
+        let options = [("--vocabulary".to_string(), "klingon".to_string())];
+        assert!(Calibrating::new(Vocabulary::english()).with_options(&options).is_err());
+        let options = [("--vocabulary".to_string(), "Cargo.toml".to_string())];
+        let error = Error::usage(
+            "Invalid --vocabulary Cargo.toml, Expected a word and its digit, e.g. 'one 1' (line 1, column 1)",
+        );
+        assert_eq!(Calibrating::new(Vocabulary::english()).with_options(&options).err(), Some(error));
         Ok(())
     }
 
     #[test]
-    fn solve_day01_part2_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day01_part2(input)?, "55701");
+    fn reporting_the_calibration() -> Result<(), String> {
+        let options = [("--report".to_string(), "table".to_string()), ("--vocabulary".to_string(), "digits".to_string())];
+        let reporting = Calibrating::new(Vocabulary::english()).with_options(&options)?;
+        assert!(!reporting.checks_answers());
+        let table = reporting.solve("a1b\nnothing".to_string())?;
+        assert_eq!(table.lines().nth(2), Some("   2  error  No digit found on line"));
+        assert_eq!(table.lines().last(), Some(" sum     11"));
+
+        let options = [("--report".to_string(), "json".to_string())];
+        let json = Calibrating::new(Vocabulary::english()).with_options(&options)?.solve("two1nine".to_string())?;
+        assert!(json.starts_with("{\n  \"sum\": 29,") && json.ends_with('}'));
+        let options = [("--report".to_string(), "xml".to_string())];
+        let error = Error::usage("Invalid --report format: xml");
+        assert_eq!(Calibrating::new(Vocabulary::english()).with_options(&options).err(), Some(error));
         Ok(())
     }
+
+    #[test]
+    fn solve_day01_part1_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART1)
+    }
+
+    #[test]
+    fn solve_day01_part2_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART2)
+    }
 }
@@ -0,0 +1,204 @@
+use std::fmt::Write;
+use crate::{first_and_last, no_digit, Match, Scanner, Vocabulary};
+
+/// A digit found on a line at the byte positions `start..end`, with the word it was spelled
+/// with, or no word when it's a digit itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Found {
+    pub digit: u32,
+    pub word: Option<String>,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Found {
+    fn new(found: Match, line: &str) -> Self {
+        let text = &line[found.start..found.end];
+        let word = match text.bytes().all(|b| b.is_ascii_digit()) {
+            true => None,
+            false => Some(text.to_string()),
+        };
+        Self { digit: found.digit, word, start: found.start, end: found.end }
+    }
+
+    fn describe(&self) -> String {
+        match &self.word {
+            Some(word) => format!("{} \"{}\" at {}..{}", self.digit, word, self.start, self.end),
+            None => format!("{} at {}..{}", self.digit, self.start, self.end),
+        }
+    }
+}
+
+/// How a line was calibrated, by its first and last digit, or why it couldn't be.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineReport {
+    /// The line number, starting from 1.
+    pub line: usize,
+    pub text: String,
+    pub calibration: Result<(Found, Found), String>,
+}
+
+impl LineReport {
+    pub fn value(&self) -> Option<u32> {
+        self.calibration.as_ref().ok().map(|(first, last)| first.digit * 10 + last.digit)
+    }
+}
+
+/// The calibration of each line, the lines without digits included as errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub lines: Vec<LineReport>,
+}
+
+/// Calibrate each line of the input for auditing, not stopping at the lines without digits.
+pub fn calibration_report(input: &str, vocabulary: &Vocabulary) -> Report {
+    let scanner = Scanner::new(vocabulary);
+    let lines = input
+        .split('\n')
+        .enumerate()
+        .map(|(i, line)| LineReport {
+            line: i + 1,
+            text: line.to_string(),
+            calibration: first_and_last(&scanner, line)
+                .map(|(first, last)| (Found::new(first, line), Found::new(last, line)))
+                .ok_or_else(|| no_digit(vocabulary).to_string()),
+        })
+        .collect();
+    Report { lines }
+}
+
+impl Report {
+    /// The sum of the calibration values of the lines that have one.
+    pub fn sum(&self) -> u32 {
+        self.lines.iter().filter_map(LineReport::value).sum()
+    }
+
+    pub fn error_count(&self) -> usize {
+        self.lines.iter().filter(|line| line.calibration.is_err()).count()
+    }
+
+    /// The report as a plain text table, one line per input line and the sum at the end.
+    pub fn to_table(&self) -> String {
+        let line_width = self.lines.len().to_string().len().max("line".len());
+        let first_width = self
+            .lines
+            .iter()
+            .filter_map(|line| line.calibration.as_ref().ok())
+            .map(|(first, _)| first.describe().chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("first".len());
+
+        let mut table = String::new();
+        let mut row = |line: &str, value: &str, rest: &str| {
+            let row = format!("{:>line_width$}  {:>5}  {}", line, value, rest);
+            writeln!(table, "{}", row.trim_end()).expect("but it's a String!?");
+        };
+        row("line", "value", &format!("{:<first_width$}  last", "first"));
+        for line in self.lines.iter() {
+            match &line.calibration {
+                Ok((first, last)) => {
+                    let value = line.value().map(|value| value.to_string()).unwrap_or_default();
+                    row(&line.line.to_string(), &value, &format!("{:<first_width$}  {}", first.describe(), last.describe()));
+                }
+                Err(message) => row(&line.line.to_string(), "error", message),
+            }
+        }
+        row("sum", &self.sum().to_string(), "");
+        table
+    }
+
+    /// The report as JSON, with the sum, the error count and the lines.
+    pub fn to_json(&self) -> String {
+        let lines = self
+            .lines
+            .iter()
+            .map(|line| {
+                let mut json = format!("{{\"line\": {}, \"text\": {}", line.line, json_string(&line.text));
+                match &line.calibration {
+                    Ok((first, last)) => {
+                        let value = line.value().expect("but the line has a calibration!?");
+                        write!(json, ", \"value\": {}, \"first\": {}, \"last\": {}", value, found_json(first), found_json(last))
+                    }
+                    Err(message) => write!(json, ", \"error\": {}", json_string(message)),
+                }
+                .expect("but it's a String!?");
+                json.push('}');
+                format!("    {}", json)
+            })
+            .collect::<Vec<_>>();
+        format!(
+            "{{\n  \"sum\": {},\n  \"errors\": {},\n  \"lines\": [\n{}\n  ]\n}}\n",
+            self.sum(),
+            self.error_count(),
+            lines.join(",\n"),
+        )
+    }
+}
+
+fn found_json(found: &Found) -> String {
+    let word = found.word.as_deref().map_or("null".to_string(), json_string);
+    format!("{{\"digit\": {}, \"word\": {}, \"start\": {}, \"end\": {}}}", found.digit, word, found.start, found.end)
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::from('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => write!(json, "\\u{:04x}", c as u32).expect("but it's a String!?"),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> Report {
+        calibration_report("two1nine\nnothing\n7pqrstsixteen", &Vocabulary::english())
+    }
+
+    #[test]
+    fn reporting_each_line() {
+        let report = report();
+        assert_eq!(report.lines.len(), 3);
+        let (first, last) = report.lines[0].calibration.clone().expect("but the line has digits!?");
+        assert_eq!(first, Found { digit: 2, word: Some("two".to_string()), start: 0, end: 3 });
+        assert_eq!(last, Found { digit: 9, word: Some("nine".to_string()), start: 4, end: 8 });
+        assert_eq!(report.lines[1].calibration, Err("No digit or digit word found on line".to_string()));
+        assert_eq!(report.lines[2].value(), Some(76));
+        assert_eq!((report.sum(), report.error_count()), (29 + 76, 1));
+
+        let report = calibration_report("a1b", &Vocabulary::digits());
+        let (first, last) = report.lines[0].calibration.clone().expect("but the line has a digit!?");
+        assert_eq!((first.word, first.start, first.end), (None, 1, 2));
+        assert_eq!(first.digit, last.digit);
+    }
+
+    #[test]
+    fn reporting_as_table_and_json() {
+        let table = report().to_table();
+        let expected = "\
+line  value  first            last
+   1     29  2 \"two\" at 0..3  9 \"nine\" at 4..8
+   2  error  No digit or digit word found on line
+   3     76  7 at 0..1        6 \"six\" at 6..9
+ sum    105
+";
+        assert_eq!(table, expected);
+
+        let json = report().to_json();
+        assert!(json.starts_with("{\n  \"sum\": 105,\n  \"errors\": 1,\n  \"lines\": [\n"));
+        assert!(json.contains(r#"{"line": 2, "text": "nothing", "error": "No digit or digit word found on line"}"#));
+        assert!(json.contains(r#""first": {"digit": 7, "word": null, "start": 0, "end": 1}"#));
+        assert_eq!(json_string("a \"b\"\\\n\u{1}"), r#""a \"b\"\\\n\u0001""#);
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::{HashMap, VecDeque};
+use crate::Vocabulary;
+
+/// A word of the vocabulary found in a text, at the byte positions `start..end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub digit: u32,
+}
+
+/// Finds the words of a vocabulary in a single pass over a text, overlapping ones included,
+/// e.g. both `eight` and `two` in `eightwo`; an Aho–Corasick automaton over the bytes.
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    next: HashMap<u8, usize>,
+    // where to continue from when the next byte doesn't follow, the longest suffix in the trie
+    fail: usize,
+    // the words ending at the node, including the ones along its fail links, as (length, digit)
+    words: Vec<(usize, u32)>,
+}
+
+impl Scanner {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        let mut nodes = vec![Node::default()];
+        for (word, digit) in vocabulary.words().filter(|(word, _)| !word.is_empty()) {
+            let mut node = 0;
+            for byte in word.bytes() {
+                node = match nodes[node].next.get(&byte) {
+                    Some(next) => *next,
+                    None => {
+                        nodes.push(Node::default());
+                        let next = nodes.len() - 1;
+                        nodes[node].next.insert(byte, next);
+                        next
+                    }
+                };
+            }
+            nodes[node].words.push((word.len(), digit));
+        }
+
+        // link the fail paths breadth first, so the shorter suffixes are linked already
+        let mut queue = nodes[0].next.values().copied().collect::<VecDeque<_>>();
+        while let Some(node) = queue.pop_front() {
+            let children = nodes[node].next.iter().map(|(byte, child)| (*byte, *child)).collect::<Vec<_>>();
+            for (byte, child) in children {
+                let fail = Self::follow(&nodes, nodes[node].fail, byte);
+                nodes[child].fail = fail;
+                let inherited = nodes[fail].words.clone();
+                nodes[child].words.extend(inherited);
+                queue.push_back(child);
+            }
+        }
+        Self { nodes }
+    }
+
+    // the node after the byte from the given node, falling back along the fail links
+    fn follow(nodes: &[Node], mut node: usize, byte: u8) -> usize {
+        loop {
+            if let Some(next) = nodes[node].next.get(&byte) {
+                return *next;
+            }
+            if node == 0 {
+                return 0;
+            }
+            node = nodes[node].fail;
+        }
+    }
+
+    /// All the words in the text, ordered by where they end, the longer first when they end together.
+    pub fn find_all(&self, text: &str) -> Vec<Match> {
+        let mut matches = vec![];
+        let mut node = 0;
+        for (i, byte) in text.bytes().enumerate() {
+            node = Self::follow(&self.nodes, node, byte);
+            for (length, digit) in self.nodes[node].words.iter() {
+                matches.push(Match { start: i + 1 - length, end: i + 1, digit: *digit });
+            }
+        }
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(matches: &[Match], text: &str) -> Vec<String> {
+        matches.iter().map(|m| format!("{}={}", &text[m.start..m.end], m.digit)).collect()
+    }
+
+    #[test]
+    fn finding_overlapping_words() {
+        let scanner = Scanner::new(&Vocabulary::english());
+        let text = "eightwothree1xoneight";
+        let matches = scanner.find_all(text);
+        assert_eq!(words(&matches, text), vec!["eight=8", "two=2", "three=3", "1=1", "one=1", "eight=8"]);
+        assert_eq!(matches[1], Match { start: 4, end: 7, digit: 2 });
+
+        assert!(scanner.find_all("zero").is_empty());
+        assert!(scanner.find_all("").is_empty());
+        assert_eq!(words(&scanner.find_all("sevenine"), "sevenine"), vec!["seven=7", "nine=9"]);
+    }
+
+    #[test]
+    fn finding_words_within_words() {
+        let vocabulary = Vocabulary::digits().with_words(["a", "bab", "abc"]);
+        let scanner = Scanner::new(&vocabulary);
+        let text = "babc";
+        assert_eq!(words(&scanner.find_all(text), text), vec!["a=1", "bab=2", "abc=3"]);
+    }
+}
@@ -0,0 +1,129 @@
+use basics::Error;
+
+/// The words that spell the digits of a calibration value, each with the digit it spells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vocabulary {
+    words: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+    /// Just the digits themselves, `0` to `9`.
+    pub fn digits() -> Self {
+        Self { words: (0..10).map(|digit| (digit.to_string(), digit)).collect() }
+    }
+
+    /// The digits and the English words for them, `one` to `nine`; no `zero`, as there's
+    /// no telling whether the calibration document means a digit or just some letters by it.
+    pub fn english() -> Self {
+        Self::digits().with_words(["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"])
+    }
+
+    /// The digits and the Finnish words for them, `yksi` to `yhdeksän`.
+    pub fn finnish() -> Self {
+        Self::digits().with_words(["yksi", "kaksi", "kolme", "neljä", "viisi", "kuusi", "seitsemän", "kahdeksan", "yhdeksän"])
+    }
+
+    /// The digits and the German words for them, `eins` to `neun`.
+    pub fn german() -> Self {
+        Self::digits().with_words(["eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun"])
+    }
+
+    /// The digits and the lowercase Roman numerals for them, `i` to `ix`; the numerals overlap
+    /// a lot, so the first digit is the longest numeral starting first and the last digit
+    /// the longest one ending last.
+    pub fn roman() -> Self {
+        Self::digits().with_words(["i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix"])
+    }
+
+    /// The names of the vocabularies built in, for `named`.
+    pub const NAMES: [&'static str; 5] = ["digits", "english", "finnish", "german", "roman"];
+
+    /// The built-in vocabulary with the name, if there is one.
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "digits" => Some(Self::digits()),
+            "english" => Some(Self::english()),
+            "finnish" => Some(Self::finnish()),
+            "german" => Some(Self::german()),
+            "roman" => Some(Self::roman()),
+            _ => None,
+        }
+    }
+
+    /// Parse the digits and custom words for them, one `<word> <digit>` per line, e.g. `one 1`;
+    /// blank lines and comment lines starting with `#`, indented or not, are skipped.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        let mut vocabulary = Self::digits();
+        for line in text.lines().filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#')) {
+            let parts = line.split_whitespace().collect::<Vec<_>>();
+            let [word, digit] = parts.as_slice() else {
+                return Err(Error::parse_at(text, line, "Expected a word and its digit, e.g. 'one 1'"));
+            };
+            let digit = digit
+                .parse::<u32>()
+                .ok()
+                .filter(|digit| *digit < 10)
+                .ok_or_else(|| Error::parse_at(text, digit, format!("Bad digit '{}', expected 0 to 9", digit)))?;
+            vocabulary.words.push((word.to_string(), digit));
+        }
+        Ok(vocabulary)
+    }
+
+    /// The vocabulary of a command line argument, either one of the `NAMES` or the path of
+    /// a file to `parse`, failing with the parse error in the file or why it couldn't be read.
+    pub fn from_argument(argument: &str) -> Result<Self, Error> {
+        if let Some(vocabulary) = Self::named(argument) {
+            return Ok(vocabulary);
+        }
+        let text = basics::read_input_file(argument).map_err(|e| {
+            Error::io(format!("{}, and it's not one of: {}", e, Self::NAMES.join(", ")))
+        })?;
+        Self::parse(&text)
+    }
+
+    /// Add words spelling the digits from one up, in order.
+    pub fn with_words<'a>(mut self, words: impl IntoIterator<Item=&'a str>) -> Self {
+        self.words.extend(words.into_iter().zip(1..).map(|(word, digit)| (word.to_string(), digit)));
+        self
+    }
+
+    pub fn words(&self) -> impl Iterator<Item=(&str, u32)> {
+        self.words.iter().map(|(word, digit)| (word.as_str(), *digit))
+    }
+
+    /// Whether any of the words spell a digit out, rather than being one.
+    pub fn has_spelled_words(&self) -> bool {
+        self.words().any(|(word, _)| !word.bytes().all(|b| b.is_ascii_digit()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_vocabularies() -> Result<(), String> {
+        let vocabulary = Vocabulary::parse("# Swedish, partly\nett 1\n\n  två 2\n  # the rest later\ntre 3")?;
+        assert_eq!(vocabulary, Vocabulary::digits().with_words(["ett", "två", "tre"]));
+        assert!(vocabulary.has_spelled_words());
+        assert!(!Vocabulary::parse("")?.has_spelled_words());
+
+        let error = Error::parse(2, 1, "Expected a word and its digit, e.g. 'one 1'");
+        assert_eq!(Vocabulary::parse("ett 1\ntvå"), Err(error));
+        let error = Error::parse(1, 5, "Bad digit '10', expected 0 to 9");
+        assert_eq!(Vocabulary::parse("tio 10"), Err(error));
+        Ok(())
+    }
+
+    #[test]
+    fn named_vocabularies() {
+        for name in Vocabulary::NAMES {
+            let vocabulary = Vocabulary::named(name).expect("but the name is one of the names!?");
+            let spelled = vocabulary.words().filter(|(word, _)| word.parse::<u32>().is_err()).count();
+            assert_eq!(spelled, if name == "digits" { 0 } else { 9 }, "{}", name);
+        }
+        assert_eq!(Vocabulary::from_argument("roman"), Ok(Vocabulary::roman()));
+        assert!(matches!(Vocabulary::from_argument("no/such/vocabulary.txt"), Err(Error::Io(_))));
+        assert_eq!(Vocabulary::named("klingon"), None);
+    }
+}
@@ -1,6 +1,6 @@
-fn main() -> Result<(), String> {
-    let input = basics::read_input()?;
-    let result = day02::solve_day02_part1(input)?;
-    println!("{}", result);
-    Ok(())
+use std::process::ExitCode;
+use day02::{Limited, Set};
+
+fn main() -> ExitCode {
+    basics::run(Limited::new(Set::elf_bag()))
 }
@@ -1,110 +1,321 @@
-use regex::{Captures, Regex};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use regex::Regex;
+use basics::{parsing, Error, Puzzle, Registry, Solver, SolverOption};
 
-pub fn solve_day02_part1(input: String) -> Result<String, String> {
-    let games = input.split("\n").map(to_game).collect::<Vec<_>>();
-    let limit = Set { red: Some(12), green: Some(13), blue: Some(14) };
+pub use stats::{game_stats, GameStats};
+
+mod stats;
+
+pub const PART1: Puzzle = Puzzle::new(2, 1, TITLE, solve_day02_part1);
+pub const PART2: Puzzle = Puzzle::new(2, 2, TITLE, solve_day02_part2);
+const TITLE: &str = "Cube Conundrum";
+
+/// Register the solvers of the day for tooling like the `aoc` runner.
+pub fn register(registry: &mut Registry) {
+    registry.register(Limited::new(Set::elf_bag()));
+    registry.register(PART2);
+}
+
+pub fn solve_day02_part1(input: String) -> Result<String, Error> {
+    possible_game_id_sum(&input, &Set::elf_bag())
+}
+
+/// The part 1 solver with the cubes in the bag given, instead of the Elf's,
+/// e.g. with `--bag "10 red, 10 green"`.
+#[derive(Debug, Clone)]
+pub struct Limited {
+    bag: Set,
+}
+
+impl Limited {
+    pub fn new(bag: Set) -> Self {
+        Self { bag }
+    }
+}
+
+impl Solver for Limited {
+    fn day(&self) -> u8 { 2 }
+    fn part(&self) -> u8 { 1 }
+    fn title(&self) -> &str { TITLE }
+    fn solve(&self, input: String) -> Result<String, Error> { possible_game_id_sum(&input, &self.bag) }
+    fn checks_answers(&self) -> bool { self.bag == Set::elf_bag() }
+    fn options(&self) -> &[SolverOption] { &[BAG_OPTION] }
+
+    fn with_options(&self, options: &[(String, String)]) -> Result<Box<dyn Solver>, Error> {
+        let mut bag = self.bag.clone();
+        for (_, cubes) in options {
+            bag = Set::parse(cubes).map_err(|e| Error::usage(format!("Invalid --bag cubes, {}", e)))?;
+        }
+        Ok(Box::new(Self::new(bag)))
+    }
+}
+
+const BAG_OPTION: SolverOption = SolverOption {
+    name: "--bag",
+    value: "<cubes>",
+    help: "the cubes in the bag instead of \"12 red, 13 green, 14 blue\",\nnot checking the answers when they differ",
+};
+
+fn possible_game_id_sum(input: &str, bag: &Set) -> Result<String, Error> {
+    let games = parsing(|| to_games(input))?;
     let possible_game_id_sum = games
         .iter()
-        .filter(|game| {
-            !game.sets.iter().any(|set| {
-                set.red > limit.red || set.green > limit.green || set.blue > limit.blue
-            })
-        })
-        .map(|game| game.id)
-        .sum::<u32>();
+        .filter(|game| game.is_possible_with(bag))
+        .try_fold(0_u32, |sum, game| sum.checked_add(game.id))
+        .ok_or_else(|| Error::no_solution("The possible game ids add up to too much"))?;
     Ok(possible_game_id_sum.to_string())
 }
 
-struct Game {
-    id: u32,
-    sets: Vec<Set>,
+/// A game of cubes drawn out of a bag, a set of them at a time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub sets: Vec<Set>,
+}
+
+impl Game {
+    /// The fewest cubes of each color the bag must have had for the game, i.e. the most
+    /// of the color drawn at once; none of the colors never drawn.
+    pub fn minimum_bag(&self) -> Set {
+        self.sets.iter().fold(Set::default(), |bag, set| bag.max(set))
+    }
+
+    /// Whether the game could have been played with the bag, i.e. it has the minimum bag.
+    pub fn is_possible_with(&self, bag: &Set) -> bool {
+        self.minimum_bag().fits_in(bag)
+    }
+
+    /// The bags the game could have been played with, of the given ones; any bag holding
+    /// the minimum bag would do.
+    pub fn feasible_bags<'a>(&self, bags: &'a [Set]) -> Vec<&'a Set> {
+        let minimum_bag = self.minimum_bag();
+        bags.iter().filter(|bag| minimum_bag.fits_in(bag)).collect()
+    }
+}
+
+/// Parse the games, one per line like `Game 1: 3 blue, 4 red; 1 red, 2 green`.
+pub fn parse_games(input: &str) -> Result<Vec<Game>, Error> {
+    to_games(input)
+}
+
+/// Cubes by color, e.g. the ones shown at once or the ones in the bag.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Set(BTreeMap<String, u32>);
+
+impl Set {
+    /// The bag of part 1, "only 12 red cubes, 13 green cubes, and 14 blue cubes".
+    pub fn elf_bag() -> Self {
+        Self::from([("red", 12), ("green", 13), ("blue", 14)])
+    }
+
+    /// Parse cubes like `3 blue, 4 red` of any colors.
+    pub fn parse(text: &str) -> Result<Self, Error> {
+        Self::parse_with(text, &cube_regex())
+    }
+
+    fn parse_with(text: &str, cube_re: &Regex) -> Result<Self, Error> {
+        let mut set = Self::default();
+        for cube_str in text.split(',') {
+            let captures = cube_re
+                .captures(cube_str)
+                .ok_or_else(|| Error::parse_at(text, cube_str.trim_start(), "Expected a count and a color, e.g. '3 blue'"))?;
+            let count_str = &captures["count"];
+            let count = count_str
+                .parse::<u32>()
+                .map_err(|e| Error::parse_at(text, count_str, format!("Bad cube count, {}", e)))?;
+            let total = set.0.entry(captures["color"].to_string()).or_default();
+            *total = total
+                .checked_add(count)
+                .ok_or_else(|| Error::parse_at(text, count_str, "Too many cubes of the color"))?;
+        }
+        Ok(set)
+    }
+
+    /// The count of the color, none when it's not in the set.
+    pub fn count(&self, color: &str) -> u32 {
+        self.0.get(color).copied().unwrap_or(0)
+    }
+
+    pub fn colors(&self) -> impl Iterator<Item=&str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    /// Whether the cubes could have come out of the bag, i.e. it has at least as many of each color.
+    pub fn fits_in(&self, bag: &Set) -> bool {
+        self.0.iter().all(|(color, count)| *count <= bag.count(color))
+    }
+
+    /// The most of each color in either set.
+    pub fn max(&self, other: &Set) -> Set {
+        let mut max = self.clone();
+        for (color, count) in other.0.iter() {
+            let most = max.0.entry(color.clone()).or_default();
+            *most = (*most).max(*count);
+        }
+        max
+    }
+
+    /// The product of the counts of the colors, zero if any of them is missing;
+    /// none when it's too much for a `u32`.
+    pub fn power<'a>(&self, colors: impl IntoIterator<Item=&'a str>) -> Option<u32> {
+        colors.into_iter().try_fold(1_u32, |power, color| power.checked_mul(self.count(color)))
+    }
+}
+
+impl<const N: usize> From<[(&str, u32); N]> for Set {
+    fn from(cubes: [(&str, u32); N]) -> Self {
+        Self(cubes.into_iter().map(|(color, count)| (color.to_string(), count)).collect())
+    }
 }
 
-struct Set {
-    red: Option<u32>,
-    green: Option<u32>,
-    blue: Option<u32>,
+impl fmt::Display for Set {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cubes = self.0.iter().map(|(color, count)| format!("{} {}", count, color)).collect::<Vec<_>>();
+        write!(f, "{}", cubes.join(", "))
+    }
 }
 
-fn to_game(line: &str) -> Game {
+// one count and color of cubes, as in `3 blue`
+fn cube_regex() -> Regex {
+    Regex::new(r"^\s*(?<count>\d+) (?<color>\w+)\s*$").expect("Bad cube regex")
+}
+
+fn to_games(input: &str) -> Result<Vec<Game>, Error> {
+    let cube_re = cube_regex();
+    input
+        .split("\n")
+        .map(|line| to_game(line, &cube_re).map_err(|e| e.within(input, line)))
+        .collect()
+}
+
+fn to_game(line: &str, cube_re: &Regex) -> Result<Game, Error> {
     let mut parts = line.split(":");
 
-    let game_str = parts.next().unwrap_or_else(|| panic!("Bad line: {}", line));
+    let game_str = parts.next().expect("but split always yields at least one part!?");
     let mut game_parts = game_str.split(" ");
     game_parts.next(); // "Game"
-    let id_str = game_parts.next().unwrap_or_else(|| panic!("Bad line: {}", line));
-    let id = id_str.parse().unwrap_or_else(|e| panic!("Bad line: {}, {}", line, e));
-
-    let sets_str = parts.next().unwrap_or_else(|| panic!("Bad line: {}", line));
-    let red_re = Regex::new(r"(?<count>\d+) red").expect("Bad red regex");
-    let green_re = Regex::new(r"(?<count>\d+) green").expect("Bad green regex");
-    let blue_re = Regex::new(r"(?<count>\d+) blue").expect("Bad blue regex");
-    fn to_u32(captures: Captures) -> Option<u32> {
-        Some(
-            captures
-                .name("count")
-                .expect("but the count group is only thing we match!?")
-                .as_str()
-                .parse()
-                .expect("but we matched a number (\\d)!?")
-        )
-    }
+    let id_str = game_parts.next().ok_or_else(|| Error::parse_at_end(game_str, "No game id"))?;
+    let id = id_str
+        .parse()
+        .map_err(|e| Error::parse_at(line, id_str, format!("Bad game id, {}", e)))?;
+
+    let sets_str = parts.next().ok_or_else(|| Error::parse_at_end(line, "No ':' after game id"))?;
     let sets = sets_str
         .split(";")
-        .map(|set_str| {
-            let red = red_re.captures(set_str).map_or(None, to_u32);
-            let green = green_re.captures(set_str).map_or(None, to_u32);
-            let blue = blue_re.captures(set_str).map_or(None, to_u32);
-            Set { red, green, blue }
-        })
-        .collect();
+        .map(|set_str| Set::parse_with(set_str, cube_re).map_err(|e| e.within(line, set_str)))
+        .collect::<Result<Vec<_>, Error>>()?;
 
-    Game { id, sets }
+    Ok(Game { id, sets })
 }
 
-pub fn solve_day02_part2(input: String) -> Result<String, String> {
-    let games = input.split("\n").map(to_game).collect::<Vec<_>>();
-    let total_power = games.iter().map(to_power).sum::<Result<u32, String>>()?;
+pub fn solve_day02_part2(input: String) -> Result<String, Error> {
+    let games = parsing(|| to_games(&input))?;
+    let colors = games
+        .iter()
+        .flat_map(|game| game.sets.iter().flat_map(Set::colors))
+        .collect::<BTreeSet<_>>();
+    let total_power = games.iter().try_fold(0_u32, |total, game| {
+        let power = game
+            .minimum_bag()
+            .power(colors.iter().copied())
+            .ok_or_else(|| Error::no_solution(format!("The power of game {} is too much", game.id)))?;
+        total.checked_add(power).ok_or_else(|| Error::no_solution("The powers of the games add up to too much"))
+    })?;
     Ok(total_power.to_string())
 }
 
-fn to_power(game: &Game) -> Result<u32, String> {
-    let max_red = game.sets.iter().filter_map(|set| set.red).max().ok_or("No reds in game")?;
-    let max_green = game.sets.iter().filter_map(|set| set.green).max().ok_or("No greens in game")?;
-    let max_blue = game.sets.iter().filter_map(|set| set.blue).max().ok_or("No blues in game")?;
-    return Ok(max_red * max_green * max_blue);
-}
-
 #[cfg(test)]
 mod tests {
-    use std::fs;
     use super::*;
 
     #[test]
-    fn solve_day02_part1_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day02_part1(input)?, "8");
-        Ok(())
+    fn bad_game_lines_are_errors() {
+        let input = "Game 1: 3 blue\nGame x: 1 red".to_string();
+        let error = Error::parse(2, 6, "Bad game id, invalid digit found in string");
+        assert_eq!(solve_day02_part1(input), Err(error));
+        let input = "Game 1: 3 blue\nGame 2 1 red".to_string();
+        let error = Error::parse(2, 13, "No ':' after game id");
+        assert_eq!(solve_day02_part1(input), Err(error));
+        let input = "Game 1: 3 blue\nGame 2: 1 red; two green".to_string();
+        let error = Error::parse(2, 16, "Expected a count and a color, e.g. '3 blue'");
+        assert_eq!(solve_day02_part1(input), Err(error));
+        let input = "Game 1: 3 blue, 99999999999 red".to_string();
+        let error = Error::parse(1, 17, "Bad cube count, number too large to fit in target type");
+        assert_eq!(solve_day02_part1(input), Err(error));
     }
 
     #[test]
-    fn solve_day02_part1_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day02_part1(input)?, "2278");
+    fn too_large_sums_and_powers_are_errors() {
+        let input = "Game 4294967295: 1 red\nGame 1: 1 red".to_string();
+        let error = Error::no_solution("The possible game ids add up to too much");
+        assert_eq!(solve_day02_part1(input), Err(error));
+        let input = "Game 1: 100000 red, 100000 green, 1 blue".to_string();
+        let error = Error::no_solution("The power of game 1 is too much");
+        assert_eq!(solve_day02_part2(input), Err(error));
+        let input = "Game 1: 65535 red, 65535 blue\nGame 2: 65535 red, 65535 blue".to_string();
+        let error = Error::no_solution("The powers of the games add up to too much");
+        assert_eq!(solve_day02_part2(input), Err(error));
+    }
+
+    #[test]
+    fn sets_of_any_colors() -> Result<(), String> {
+        let set = Set::parse(" 3 blue, 4 red, 1 mauve")?;
+        assert_eq!(set, Set::from([("blue", 3), ("mauve", 1), ("red", 4)]));
+        assert_eq!(set.to_string(), "3 blue, 1 mauve, 4 red");
+        assert_eq!((set.count("mauve"), set.count("green")), (1, 0));
+        assert!(!set.fits_in(&Set::elf_bag()));
+        assert!(Set::parse("3 blue, 4 red")?.fits_in(&Set::elf_bag()));
+        assert!(Set::parse("3 blue, 4 red")?.fits_in(&Set::parse("4 red, 3 blue, 2 mauve")?));
+
+        assert_eq!(Set::parse("3 blue, 2 blue")?, Set::from([("blue", 5)]));
+        let error = Error::parse(1, 17, "Too many cubes of the color");
+        assert_eq!(Set::parse("4294967295 red, 1 red"), Err(error));
+        let error = Error::parse(1, 25, "Too many cubes of the color");
+        assert_eq!(solve_day02_part1("Game 1: 4294967295 red, 1 red".to_string()), Err(error));
         Ok(())
     }
 
     #[test]
-    fn solve_day02_part2_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day02_part2(input)?, "2286");
+    fn minimum_and_feasible_bags() -> Result<(), String> {
+        let games = parse_games("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\nGame 2: 2 red; 1 red")?;
+        assert_eq!(games[0].minimum_bag(), Set::parse("4 red, 2 green, 6 blue")?);
+        assert_eq!(games[1].minimum_bag(), Set::parse("2 red")?);
+        assert_eq!(games[1].minimum_bag().power(["red", "green", "blue"]), Some(0));
+        assert_eq!(games[0].minimum_bag().power(["red", "green", "blue"]), Some(48));
+
+        let bags = [Set::elf_bag(), Set::parse("2 red, 9 blue")?, Set::parse("5 red, 2 green, 6 blue, 1 mauve")?];
+        assert_eq!(games[0].feasible_bags(&bags), vec![&bags[0], &bags[2]]);
+        assert_eq!(games[1].feasible_bags(&bags).len(), 3);
+        assert!(!games[0].is_possible_with(&Set::default()));
         Ok(())
     }
 
     #[test]
-    fn solve_day02_part2_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day02_part2(input)?, "67953");
+    fn possible_games_in_other_bags() -> Result<(), String> {
+        let input = basics::read_input_file("examples/example.txt")?;
+        assert_eq!(Limited::new(Set::elf_bag()).solve(input.clone())?, solve_day02_part1(input.clone())?);
+        assert_eq!(Limited::new(Set::parse("20 red, 13 green, 15 blue")?).solve(input.clone())?, "15");
+        assert_eq!(Limited::new(Set::default()).solve(input.clone())?, "0");
+        assert!(!Limited::new(Set::default()).checks_answers());
+        let options = [("--bag".to_string(), "20 red, 13 green, 15 blue".to_string())];
+        assert_eq!(Limited::new(Set::elf_bag()).with_options(&options)?.solve(input.clone())?, "15");
+        let options = [("--bag".to_string(), "20 red, 13".to_string())];
+        assert!(Limited::new(Set::elf_bag()).with_options(&options).is_err());
+
+        let input = "Game 1: 3 blue, 1 mauve\nGame 2: 2 red, 6 blue\nGame 3: 1 mauve; 2 mauve".to_string();
+        assert_eq!(Limited::new(Set::parse("6 blue, 2 red, 1 mauve")?).solve(input.clone())?, "3");
+        assert_eq!(solve_day02_part2(input)?, "0"); // each game is missing a color
         Ok(())
     }
+
+    #[test]
+    fn solve_day02_part1_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART1)
+    }
+
+    #[test]
+    fn solve_day02_part2_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART2)
+    }
 }
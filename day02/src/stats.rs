@@ -0,0 +1,72 @@
+use basics::Error;
+use crate::{Game, Set};
+
+/// Statistics over all the games played.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameStats {
+    pub games: usize,
+    /// The sets of cubes drawn in all the games.
+    pub draws: usize,
+    /// The game with the most sets drawn, as `(id, draws)`, the first one of those tied.
+    pub most_draws: Option<(u32, usize)>,
+    /// The most of each color drawn at once in any game, i.e. the minimum bag for all the games.
+    pub largest_draws: Set,
+    /// All the cubes drawn in all the games, by color.
+    pub cubes_drawn: Set,
+    /// The color of the most cubes drawn, as `(color, cubes)`, the first alphabetically of those tied.
+    pub most_common_color: Option<(String, u32)>,
+}
+
+/// The statistics of the games, unless the cubes drawn of a color add up to too many to count.
+pub fn game_stats(games: &[Game]) -> Result<GameStats, Error> {
+    let mut cubes_drawn = Set::default();
+    for set in games.iter().flat_map(|game| game.sets.iter()) {
+        for (color, count) in set.0.iter() {
+            let total = cubes_drawn.0.entry(color.clone()).or_default();
+            *total = total
+                .checked_add(*count)
+                .ok_or_else(|| Error::no_solution(format!("Too many {} cubes drawn to count", color)))?;
+        }
+    }
+    let most_common_color = cubes_drawn
+        .0
+        .iter()
+        .max_by(|(color_a, a), (color_b, b)| a.cmp(b).then(color_b.cmp(color_a)))
+        .map(|(color, count)| (color.clone(), *count));
+    Ok(GameStats {
+        games: games.len(),
+        draws: games.iter().map(|game| game.sets.len()).sum(),
+        most_draws: games
+            .iter()
+            .map(|game| (game.id, game.sets.len()))
+            .reduce(|most, game| if game.1 > most.1 { game } else { most }),
+        largest_draws: games.iter().fold(Set::default(), |bag, game| bag.max(&game.minimum_bag())),
+        cubes_drawn,
+        most_common_color,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_games;
+
+    #[test]
+    fn stats_over_games() -> Result<(), String> {
+        let input = basics::read_input_file("examples/example.txt")?;
+        let stats = game_stats(&parse_games(&input)?)?;
+        assert_eq!((stats.games, stats.draws), (5, 14));
+        assert_eq!(stats.most_draws, Some((1, 3)));
+        assert_eq!(stats.largest_draws, Set::parse("20 red, 13 green, 15 blue")?);
+        assert_eq!(stats.cubes_drawn, Set::parse("61 red, 48 green, 50 blue")?);
+        assert_eq!(stats.most_common_color, Some(("red".to_string(), 61)));
+
+        let stats = game_stats(&parse_games("Game 7: 2 red; 2 blue")?)?;
+        assert_eq!(stats.most_common_color, Some(("blue".to_string(), 2)));
+        let stats = game_stats(&[])?;
+        assert_eq!((stats.most_draws, stats.most_common_color), (None, None));
+        let error = Error::no_solution("Too many red cubes drawn to count");
+        assert_eq!(game_stats(&parse_games("Game 1: 4294967295 red; 1 red")?), Err(error));
+        Ok(())
+    }
+}
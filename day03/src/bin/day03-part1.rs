@@ -1,6 +1,5 @@
-fn main() -> Result<(), String> {
-    let input = basics::read_input()?;
-    let result = day03::solve_day03_part1(input)?;
-    println!("{}", result);
-    Ok(())
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    basics::run(day03::PART1)
 }
@@ -1,184 +1,276 @@
+use std::collections::BTreeMap;
 use regex::Regex;
+use basics::{parsing, Error, Puzzle, Registry};
+use grid::{Grid, Point};
 
-pub fn solve_day03_part1(input: String) -> Result<String, String> {
-    let schematic = Schematic::new(&input);
-    Ok(schematic.part_numbers.iter().map(|pn| pn.value).sum::<usize>().to_string())
+pub const PART1: Puzzle = Puzzle::new(3, 1, TITLE, solve_day03_part1);
+pub const PART2: Puzzle = Puzzle::new(3, 2, TITLE, solve_day03_part2);
+const TITLE: &str = "Gear Ratios";
+
+/// Register the solvers of the day for tooling like the `aoc` runner.
+pub fn register(registry: &mut Registry) {
+    registry.register(PART1);
+    registry.register(PART2);
+}
+
+pub fn solve_day03_part1(input: String) -> Result<String, Error> {
+    let schematic = parsing(|| Schematic::new(&input))?;
+    let part_number_sum = schematic
+        .part_numbers
+        .iter()
+        .try_fold(0_usize, |sum, pn| sum.checked_add(pn.value))
+        .ok_or_else(|| Error::no_solution("The part numbers add up to too much"))?;
+    Ok(part_number_sum.to_string())
+}
+
+pub fn solve_day03_part2(input: String) -> Result<String, Error> {
+    let schematic = parsing(|| Schematic::new(&input))?;
+    let sum_of_gear_ratios = schematic
+        .gears(&GearRule::default())?
+        .iter()
+        .try_fold(0_usize, |sum, (_, ratio)| sum.checked_add(*ratio))
+        .ok_or_else(|| Error::no_solution("The gear ratios add up to too much"))?;
+    Ok(sum_of_gear_ratios.to_string())
 }
 
-struct Schematic {
-    grid: Vec<Vec<String>>,
+/// An engine schematic, its part numbers and what symbols they are next to.
+pub struct Schematic {
+    grid: Grid<char>,
     part_numbers: Vec<PartNumber>,
+    // the index from each symbol to the part numbers around it, by their index;
+    // keyed by (y, x) rather than the point, to keep the symbols row by row
+    symbol_neighbors: BTreeMap<(usize, usize), Vec<usize>>,
 }
 
 impl Schematic {
-    fn new(input: &String) -> Self {
-        let grid = input
-            .lines()
-            .map(|row| row.chars().map(|c| c.to_string()).collect::<Vec<_>>())
-            .collect::<Vec<_>>();
+    pub fn new(input: &str) -> Result<Self, Error> {
+        let grid = Grid::parse(input, Ok)?;
+        let mut symbol_neighbors = grid
+            .iter()
+            .filter(|(_, cell)| is_symbol(**cell))
+            .map(|((x, y), _)| ((y, x), vec![]))
+            .collect::<BTreeMap<_, _>>();
 
         // we do the part number finding here because we still have schematic rows
-        // as strings, and we don't need them as strings after that
-        let symbol_re = Regex::new(r"[^\d\.]").expect("Bad red regex");
-        let number_re = Regex::new(r"\d+").expect("Bad number regex");
-        let part_numbers = input
-            .lines()
-            .enumerate()
-            .flat_map(|(row_index, row)| {
-                let row_part_numbers = number_re
-                    .captures_iter(row)
-                    .filter_map(|num_capture| {
-                        let Some(num_match) = num_capture.get(0) else { return None; };
-                        let num_start = num_match.start();
-                        let num_end = num_match.end() - 1;
-                        let is_part_number = adjacents(row_index, num_start, num_end)
-                            .into_iter()
-                            .any(|adj| {
-                                match grid.get(adj.0).and_then(|row| row.get(adj.1)) {
-                                    Some(cell) => symbol_re.is_match(cell),
-                                    _ => false,
-                                }
-                            });
-                        match is_part_number {
-                            true => {
-                                let number = num_match
-                                    .as_str()
-                                    .parse::<usize>()
-                                    .expect("but we matched \\d!?");
-                                Some(PartNumber {
-                                    value: number,
-                                    row: row_index,
-                                    start: num_start,
-                                    end: num_end,
-                                })
-                            }
-                            _ => None,
-                        }
-                    })
+        // as strings, and we don't need them as strings after that; only ASCII digits
+        // make numbers, any other digits are symbols like the rest
+        let number_re = Regex::new(r"[0-9]+").expect("Bad number regex");
+        let mut part_numbers = vec![];
+        for (row_index, row) in input.lines().enumerate() {
+            for num_match in number_re.find_iter(row) {
+                // the grid is in characters and the match in bytes, with symbols like '€' before it;
+                // the digits themselves are all one byte though
+                let start = row[..num_match.start()].chars().count();
+                let end = start + num_match.len() - 1;
+                // the only time the surroundings of the number are looked at
+                let symbols = adjacents(row_index, start, end)
+                    .into_iter()
+                    .filter(|adj| grid.get(*adj).is_some_and(|cell| is_symbol(*cell)))
                     .collect::<Vec<_>>();
-                row_part_numbers
-            }).collect::<Vec<_>>();
+                if symbols.is_empty() {
+                    continue; // not a part number
+                }
+                for (x, y) in symbols {
+                    symbol_neighbors.entry((y, x)).or_default().push(part_numbers.len());
+                }
+                let value = num_match
+                    .as_str()
+                    .parse::<usize>()
+                    .map_err(|e| Error::parse_at(input, num_match.as_str(), format!("Bad part number, {}", e)))?;
+                part_numbers.push(PartNumber { value, row: row_index, start, end });
+            }
+        }
 
-        Self { grid, part_numbers }
+        Ok(Self { grid, part_numbers, symbol_neighbors })
     }
 
-    fn asterisks(&self) -> Vec<(usize, usize)> {
-        self.grid
+    pub fn part_numbers(&self) -> &[PartNumber] {
+        &self.part_numbers
+    }
+
+    /// Every symbol of the schematic, row by row, with the part numbers around it.
+    pub fn symbols(&self) -> Vec<Symbol> {
+        self.symbol_neighbors
             .iter()
-            .enumerate()
-            .flat_map(|(row_index, row)| {
-                row.iter()
-                    .enumerate()
-                    .filter_map(|(col_index, cell)| {
-                        match cell.as_str() {
-                            "*" => Some((row_index, col_index)),
-                            _ => None,
-                        }
-                    })
-                    .collect::<Vec<_>>()
+            .map(|((y, x), neighbors)| Symbol {
+                point: (*x, *y),
+                symbol: self.grid[(*x, *y)],
+                part_numbers: neighbors.iter().map(|i| self.part_numbers[*i].value).collect(),
             })
             .collect::<Vec<_>>()
     }
+
+    /// The gears by the rule, with their gear ratios, row by row;
+    /// an error if a gear ratio is too large to count.
+    pub fn gears(&self, rule: &GearRule) -> Result<Vec<(Point, usize)>, Error> {
+        self.symbols()
+            .into_iter()
+            .filter(|symbol| rule.symbols.contains(&symbol.symbol) && symbol.part_numbers.len() == rule.neighbors)
+            .map(|symbol| {
+                let ratio = rule.combine.apply(&symbol.part_numbers).ok_or_else(|| {
+                    Error::no_solution(format!("The gear ratio at {:?} is too large", symbol.point))
+                })?;
+                Ok((symbol.point, ratio))
+            })
+            .collect()
+    }
 }
 
-struct PartNumber {
-    value: usize,
-    row: usize,
-    start: usize,
-    end: usize,
+fn is_symbol(cell: char) -> bool {
+    !cell.is_ascii_digit() && cell != '.'
 }
 
-impl PartNumber {
-    fn is_adjacent_to(&self, position: &(usize, usize)) -> bool {
-        adjacents(self.row, self.start, self.end).contains(position)
+/// A symbol in the schematic and the values of the part numbers around it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    pub point: Point,
+    pub symbol: char,
+    pub part_numbers: Vec<usize>,
+}
+
+/// What makes a symbol a gear and how its part numbers make up the gear ratio.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GearRule {
+    pub symbols: Vec<char>,
+    /// The exact number of part numbers around the symbol.
+    pub neighbors: usize,
+    pub combine: Combine,
+}
+
+impl Default for GearRule {
+    /// The gears of part 2, any `*` next to exactly two part numbers, multiplied.
+    fn default() -> Self {
+        Self { symbols: vec!['*'], neighbors: 2, combine: Combine::Product }
     }
 }
 
-fn adjacents(row: usize, start: usize, end: usize) -> Vec<(usize, usize)> {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Combine { Sum, Product }
+
+impl Combine {
+    /// The values combined, none when that's too large for a `usize`.
+    fn apply(&self, values: &[usize]) -> Option<usize> {
+        match self {
+            Combine::Sum => values.iter().try_fold(0_usize, |sum, value| sum.checked_add(*value)),
+            Combine::Product => values.iter().try_fold(1_usize, |product, value| product.checked_mul(*value)),
+        }
+    }
+}
+
+/// A number next to a symbol, on the given row spanning the columns from start to end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartNumber {
+    pub value: usize,
+    pub row: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+// the points around a number on the given row, spanning the columns from start to end
+fn adjacents(row: usize, start: usize, end: usize) -> Vec<Point> {
     let mut positions = Vec::new();
 
     // previous row
     if row > 0 {
-        if start > 0 { positions.push((row - 1, start - 1)); }
-        for i in start..=end + 1 { positions.push((row - 1, i)); }
+        if start > 0 { positions.push((start - 1, row - 1)); }
+        for i in start..=end + 1 { positions.push((i, row - 1)); }
     }
 
     // this row
-    if start > 0 { positions.push((row, start - 1)); }
-    positions.push((row, end + 1));
+    if start > 0 { positions.push((start - 1, row)); }
+    positions.push((end + 1, row));
 
     // next row
-    if start > 0 { positions.push((row + 1, start - 1)); }
-    for i in start..=end + 1 { positions.push((row + 1, i)); }
+    if start > 0 { positions.push((start - 1, row + 1)); }
+    for i in start..=end + 1 { positions.push((i, row + 1)); }
 
     positions
 }
 
-pub fn solve_day03_part2(input: String) -> Result<String, String> {
-    let schematic = Schematic::new(&input);
-    let sum_of_gear_ratios = schematic
-        .asterisks()
-        .iter()
-        .map(|asterisk| {
-            let adj_part_numbers = schematic.part_numbers
-                .iter()
-                .filter(|part_number| part_number.is_adjacent_to(asterisk))
-                .collect::<Vec<_>>();
-            if adj_part_numbers.len() != 2 { return 0; } // this asterisk is not "gear" ❌⚙️
-            adj_part_numbers
-                .iter()
-                .map(|part_number| part_number.value)
-                .product::<usize>()
-        })
-        .sum::<usize>();
-    Ok(sum_of_gear_ratios.to_string())
-}
-
 #[cfg(test)]
 mod tests {
-    use std::fs;
     use super::*;
 
     #[test]
     fn adjacent_works() -> Result<(), String> {
         assert_eq!(adjacents(0, 0, 3), vec![
             // the previous row is out of grid
-            (0, 4), // the current row is half-out of grid
-            (1, 0), (1, 1), (1, 2), (1, 3), (1, 4), // the next row has one position out of grid
+            (4, 0), // the current row is half-out of grid
+            (0, 1), (1, 1), (2, 1), (3, 1), (4, 1), // the next row has one position out of grid
         ]);
         assert_eq!(adjacents(2, 3, 4), vec![
-            (1, 2), (1, 3), (1, 4), (1, 5), // the previous row
-            (2, 2), (2, 5),  // the current row
-            (3, 2), (3, 3), (3, 4), (3, 5), // the next row
+            (2, 1), (3, 1), (4, 1), (5, 1), // the previous row
+            (2, 2), (5, 2),  // the current row
+            (2, 3), (3, 3), (4, 3), (5, 3), // the next row
         ]);
         Ok(())
     }
 
     #[test]
-    fn solve_day03_part1_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day03_part1(input)?, "4361");
+    fn symbols_and_their_part_numbers() -> Result<(), String> {
+        let input = basics::read_input_file("examples/example.txt")?;
+        let schematic = Schematic::new(&input)?;
+        assert_eq!(schematic.part_numbers().len(), 8);
+        assert_eq!(schematic.part_numbers()[0], PartNumber { value: 467, row: 0, start: 0, end: 2 });
+        let symbols = schematic.symbols();
+        assert_eq!(symbols.len(), 6);
+        assert_eq!(symbols[0], Symbol { point: (3, 1), symbol: '*', part_numbers: vec![467, 35] });
+        assert_eq!(symbols[1], Symbol { point: (6, 3), symbol: '#', part_numbers: vec![633] });
+        assert_eq!(symbols[2].part_numbers, vec![617]); // a * but not a gear
+
+        // a number next to two symbols is counted for both
+        let schematic = Schematic::new("2.3\n*1$")?;
+        let symbols = schematic.symbols().into_iter().map(|s| (s.symbol, s.part_numbers)).collect::<Vec<_>>();
+        assert_eq!(symbols, vec![('*', vec![2, 1]), ('$', vec![3, 1])]);
+
+        // the columns are in characters, not bytes
+        let schematic = Schematic::new("€€..12\n....→.\n7.....")?;
+        assert_eq!(schematic.part_numbers(), [PartNumber { value: 12, row: 0, start: 4, end: 5 }]);
+        assert_eq!(schematic.symbols()[2], Symbol { point: (4, 1), symbol: '→', part_numbers: vec![12] });
         Ok(())
     }
 
     #[test]
-    fn solve_day03_part1_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day03_part1(input)?, "530849");
+    fn gears_by_other_rules() -> Result<(), String> {
+        let input = basics::read_input_file("examples/example.txt")?;
+        let schematic = Schematic::new(&input)?;
+        assert_eq!(schematic.gears(&GearRule::default())?, vec![((3, 1), 467 * 35), ((5, 8), 755 * 598)]);
+
+        let rule = GearRule { symbols: vec!['*', '#', '+'], neighbors: 1, combine: Combine::Sum };
+        assert_eq!(schematic.gears(&rule)?, vec![((6, 3), 633), ((3, 4), 617), ((5, 5), 592)]);
+        let rule = GearRule { symbols: vec!['*'], neighbors: 2, combine: Combine::Sum };
+        assert_eq!(schematic.gears(&rule)?.iter().map(|(_, ratio)| ratio).sum::<usize>(), 467 + 35 + 755 + 598);
+        let rule = GearRule { symbols: vec!['?'], ..GearRule::default() };
+        assert!(schematic.gears(&rule)?.is_empty());
         Ok(())
     }
 
     #[test]
-    fn solve_day03_part2_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day03_part2(input)?, "467835");
-        Ok(())
+    fn too_large_numbers_are_errors() {
+        let input = "..99999999999999999999999*".to_string();
+        let error = Error::parse(1, 3, "Bad part number, number too large to fit in target type");
+        assert_eq!(solve_day03_part1(input), Err(error));
+        // other digits than ASCII are symbols
+        assert_eq!(solve_day03_part1("1٣".to_string()), Ok("1".to_string()));
+
+        let input = "18446744073709551615*\n.....................\n18446744073709551615$".to_string();
+        let error = Error::no_solution("The part numbers add up to too much");
+        assert_eq!(solve_day03_part1(input), Err(error));
+        let input = "4294967296*4294967296".to_string();
+        let error = Error::no_solution("The gear ratio at (10, 0) is too large");
+        assert_eq!(solve_day03_part2(input), Err(error));
+        let input = "4294967295*4294967295\n.....................\n4294967295*4294967295".to_string();
+        let error = Error::no_solution("The gear ratios add up to too much");
+        assert_eq!(solve_day03_part2(input), Err(error));
     }
 
     #[test]
-    fn solve_day03_part2_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day03_part2(input)?, "84900879");
-        Ok(())
+    fn solve_day03_part1_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART1)
+    }
+
+    #[test]
+    fn solve_day03_part2_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART2)
     }
 }
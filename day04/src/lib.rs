@@ -1,7 +1,18 @@
 use std::collections::HashSet;
+use basics::{parsing, Error, Puzzle, Registry};
 
-pub fn solve_day04_part1(input: String) -> Result<String, String> {
-    let cards = input.lines().map(Card::from).collect::<Vec<_>>();
+pub const PART1: Puzzle = Puzzle::new(4, 1, TITLE, solve_day04_part1);
+pub const PART2: Puzzle = Puzzle::new(4, 2, TITLE, solve_day04_part2);
+const TITLE: &str = "Scratchcards";
+
+/// Register the solvers of the day for tooling like the `aoc` runner.
+pub fn register(registry: &mut Registry) {
+    registry.register(PART1);
+    registry.register(PART2);
+}
+
+pub fn solve_day04_part1(input: String) -> Result<String, Error> {
+    let cards = parsing(|| Card::many_from(&input))?;
     let score = cards.iter().map(|c| c.point_score()).sum::<usize>();
     Ok(score.to_string())
 }
@@ -13,30 +24,41 @@ struct Card {
 }
 
 impl Card {
-    fn from(line: &str) -> Card {
+    fn from(line: &str) -> Result<Card, Error> {
         let mut parts = line.split(":");
 
-        let card_str = parts.next().unwrap_or_else(|| panic!("Bad card line {}", line));
+        let card_str = parts.next().expect("but split always yields at least one part!?");
         let mut card_parts = card_str.split_whitespace();
         card_parts.next(); // skip the "Card" part
-        let id_str = card_parts.next().unwrap_or_else(|| panic!("Bad card id {}", line));
-        let _id = id_str.parse::<usize>().unwrap_or_else(|_e| panic!("Bad card id {}", line));
+        let id_str = card_parts.next().ok_or_else(|| Error::parse_at_end(card_str, "No card id"))?;
+        let _id = id_str
+            .parse::<usize>()
+            .map_err(|_e| Error::parse_at(line, id_str, "Bad card id"))?;
 
-        let numbers_str = parts.next().unwrap_or_else(|| panic!("Bad card line {}", line));
+        let numbers_str = parts.next().ok_or_else(|| Error::parse_at_end(line, "No ':' after card id"))?;
         let mut numbers_parts = numbers_str.split("|");
-        let win_numbers_str = numbers_parts.next().unwrap_or_else(|| panic!("Bad numbers {}", line));
-        let your_numbers_str = numbers_parts.next().unwrap_or_else(|| panic!("Bad numbers {}", line));
-
-        let win_numbers = win_numbers_str
-            .split_whitespace()
-            .map(|s| s.parse::<usize>().unwrap_or_else(|_e| panic!("Bad win number {}", line)))
-            .collect::<HashSet<_>>();
-        let your_numbers = your_numbers_str
-            .split_whitespace()
-            .map(|s| s.parse::<usize>().unwrap_or_else(|_e| panic!("Bad your number {}", line)))
-            .collect::<HashSet<_>>();
-
-        Card { win_numbers, your_numbers }
+        let win_numbers_str = numbers_parts.next().expect("but split always yields at least one part!?");
+        let your_numbers_str = numbers_parts
+            .next()
+            .ok_or_else(|| Error::parse_at_end(line, "No '|' between the numbers"))?;
+
+        let parse_numbers = |numbers_str: &str, message: &str| {
+            numbers_str
+                .split_whitespace()
+                .map(|s| s.parse::<usize>().map_err(|_e| Error::parse_at(line, s, message)))
+                .collect::<Result<HashSet<_>, _>>()
+        };
+        let win_numbers = parse_numbers(win_numbers_str, "Bad win number")?;
+        let your_numbers = parse_numbers(your_numbers_str, "Bad your number")?;
+
+        Ok(Card { win_numbers, your_numbers })
+    }
+
+    fn many_from(input: &str) -> Result<Vec<Card>, Error> {
+        input
+            .lines()
+            .map(|line| Card::from(line).map_err(|e| e.within(input, line)))
+            .collect()
     }
 
     fn match_count(&self) -> usize {
@@ -59,8 +81,8 @@ impl Card {
 }
 
 
-pub fn solve_day04_part2(input: String) -> Result<String, String> {
-    let cards = input.lines().map(Card::from).collect::<Vec<_>>();
+pub fn solve_day04_part2(input: String) -> Result<String, Error> {
+    let cards = parsing(|| Card::many_from(&input))?;
     let mut card_count = cards.len();
     let mut scan_indices: Vec<usize> = (0..card_count).collect();
     while !scan_indices.is_empty() {
@@ -79,34 +101,23 @@ pub fn solve_day04_part2(input: String) -> Result<String, String> {
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
     use super::*;
 
     #[test]
-    fn solve_day04_part1_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day04_part1(input)?, "13");
-        Ok(())
-    }
-
-    #[test]
-    fn solve_day04_part1_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day04_part1(input)?, "23441");
-        Ok(())
+    fn bad_card_lines_are_errors() {
+        let input = "Card 1: 41 48 | 83 86\nCard 2: 13 x2 | 61 30";
+        assert_eq!(Card::many_from(input).err(), Some(Error::parse(2, 12, "Bad win number")));
+        let input = "Card 1: 41 48 | 83 86\nCard 2: 13 32";
+        assert_eq!(Card::many_from(input).err(), Some(Error::parse(2, 14, "No '|' between the numbers")));
     }
 
     #[test]
-    fn solve_day04_part2_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day04_part2(input)?, "30");
-        Ok(())
+    fn solve_day04_part1_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART1)
     }
 
     #[test]
-    fn solve_day04_part2_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day04_part2(input)?, "5923918");
-        Ok(())
+    fn solve_day04_part2_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART2)
     }
 }
@@ -1,14 +1,30 @@
-pub fn solve_day05_part1(input: String) -> Result<String, String> {
-    let almanac = Almanac::from(input)?;
+use basics::{parsing, Error, Puzzle, Registry};
+
+pub const PART1: Puzzle = Puzzle::new(5, 1, TITLE, solve_day05_part1);
+pub const PART2: Puzzle = Puzzle::new(5, 2, TITLE, solve_day05_part2);
+const TITLE: &str = "If You Give A Seed A Fertilizer";
+
+/// Register the solvers of the day for tooling like the `aoc` runner.
+pub fn register(registry: &mut Registry) {
+    registry.register(PART1);
+    registry.register(PART2);
+}
+
+pub fn solve_day05_part1(input: String) -> Result<String, Error> {
+    let almanac = parsing(|| Almanac::from(&input))?;
     let final_forms = almanac.apply_all_tables_to_seeds();
-    let smallest = final_forms.iter().min().ok_or("No smallest value found")?;
+    let smallest = final_forms.iter().min().ok_or(Error::no_solution("No seeds"))?;
     Ok(smallest.to_string())
 }
 
-pub fn solve_day05_part2(input: String) -> Result<String, String> {
-    let almanac = Almanac::from(input)?;
+pub fn solve_day05_part2(input: String) -> Result<String, Error> {
+    let almanac = parsing(|| Almanac::from_seed_ranges(&input))?;
     let ranges = almanac.apply_all_tables_to_seed_ranges();
-    let smallest_low_bound = ranges.iter().map(|r| r.0).min().ok_or("No smallest value found")?;
+    let smallest_low_bound = ranges
+        .iter()
+        .map(|r| r.0)
+        .min()
+        .ok_or(Error::no_solution("No seed ranges"))?;
     Ok(smallest_low_bound.to_string())
 }
 
@@ -18,20 +34,41 @@ struct Almanac {
 }
 
 impl Almanac {
-    fn from(input: String) -> Result<Self, String> {
+    fn from(input: &str) -> Result<Self, Error> {
         let mut parts = input.split("\n\n");
 
-        let seeds_str = parts.next().ok_or(format!("Bad seeds line: {}", input))?;
+        let seeds_str = parts.next().expect("but split always yields at least one part!?");
         let seeds_parts = seeds_str.split_whitespace().skip(1); // discard "seeds:" 🚮
         let seeds = seeds_parts
-            .map(|s| s.parse::<isize>().map_err(|e| e.to_string()))
-            .collect::<Result<Vec<_>, String>>()?;
+            .map(|s| s.parse::<isize>().map_err(|e| Error::parse_at(input, s, e.to_string())))
+            .collect::<Result<Vec<_>, Error>>()?;
 
-        let tables = parts.map(Table::from).collect::<Result<Vec<_>, String>>()?;
+        let tables = parts
+            .map(|part| Table::from(part).map_err(|e| e.within(input, part)))
+            .collect::<Result<Vec<_>, Error>>()?;
 
         Ok(Self { seeds, tables })
     }
 
+    /// The almanac with the seeds as pairs of a range start and a length, as in part 2.
+    fn from_seed_ranges(input: &str) -> Result<Self, Error> {
+        let almanac = Self::from(input)?;
+        let seeds_str = input.split("\n\n").next().expect("but split always yields at least one part!?");
+        let seeds_parts = seeds_str.split_whitespace().skip(1).collect::<Vec<_>>();
+        for (pair, seeds) in seeds_parts.chunks(2).zip(almanac.seeds.chunks(2)) {
+            let &[_, length_str] = pair else {
+                return Err(Error::parse_at_end(seeds_str, "Seed range is missing its length"));
+            };
+            if seeds[1] <= 0 {
+                return Err(Error::parse_at(input, length_str, "Seed range length must be positive"));
+            }
+            if seeds[0].checked_add(seeds[1]).is_none() {
+                return Err(Error::parse_at(input, length_str, "Seed range is too long"));
+            }
+        }
+        Ok(almanac)
+    }
+
     fn apply_all_tables_to_seeds(&self) -> Vec<isize> {
         let mut final_form = self.seeds.clone();
         for table in &self.tables {
@@ -42,7 +79,7 @@ impl Almanac {
 
     fn apply_all_tables_to_seed_ranges(&self) -> Vec<Range> {
         let mut ranges = self.seeds
-            .chunks(2)
+            .chunks_exact(2)
             .map(|chunk| {
                 let start = chunk[0];
                 let end = start + chunk[1] - 1;
@@ -60,22 +97,28 @@ impl Almanac {
 }
 
 struct Table {
+    #[allow(dead_code)] // only used for debugging
     source_type: String,
+    #[allow(dead_code)] // only used for debugging
     destination_type: String,
     transforms: Vec<Transform>,
 }
 
 impl Table {
-    fn from(input: &str) -> Result<Self, String> {
+    fn from(input: &str) -> Result<Self, Error> {
         let mut lines = input.lines();
 
-        let mut header = lines.next().ok_or(format!("Bad table: {}", input))?.split_whitespace();
-        let name = header.next().ok_or(format!("Bad table header: {}", input))?;
+        let mut header = lines.next().ok_or(Error::parse(1, 1, "No table header"))?.split_whitespace();
+        let name = header.next().ok_or(Error::parse(1, 1, "No table name"))?;
         let mut name_parts = name.split("-to-");
-        let source = name_parts.next().ok_or(format!("Bad table name: {}", input))?;
-        let destination = name_parts.next().ok_or(format!("Bad table name: {}", input))?;
+        let source = name_parts.next().expect("but split always yields at least one part!?");
+        let destination = name_parts
+            .next()
+            .ok_or_else(|| Error::parse_at(input, name, "Table name is not like 'alpha-to-beta'"))?;
 
-        let transforms = lines.map(Transform::from).collect::<Result<Vec<_>, String>>()?;
+        let transforms = lines
+            .map(|line| Transform::from(line).map_err(|e| e.within(input, line)))
+            .collect::<Result<Vec<_>, Error>>()?;
         Ok(Self {
             source_type: source.to_string(),
             destination_type: destination.to_string(),
@@ -121,18 +164,18 @@ struct Transform {
 }
 
 impl Transform {
-    fn from(input: &str) -> Result<Self, String> {
+    fn from(input: &str) -> Result<Self, Error> {
         let mut parts = input.split_whitespace();
-        let destination_start = parts
-            .next().ok_or(format!("Bad transform destination range start: {}", input))?
-            .parse::<isize>().map_err(|e| e.to_string())?;
-        let source_start = parts
-            .next().ok_or(format!("Bad transform source range start: {}", input))?
-            .parse::<isize>().map_err(|e| e.to_string())?;
-        let range_length = parts
-            .next()
-            .ok_or(format!("Bad transform range length: {}", input))?
-            .parse::<isize>().map_err(|e| e.to_string())?;
+        let mut next_number = |name: &str| {
+            let part = parts
+                .next()
+                .ok_or_else(|| Error::parse_at_end(input, format!("No transform {}", name)))?;
+            part.parse::<isize>()
+                .map_err(|e| Error::parse_at(input, part, format!("Bad transform {}, {}", name, e)))
+        };
+        let destination_start = next_number("destination range start")?;
+        let source_start = next_number("source range start")?;
+        let range_length = next_number("range length")?;
         Ok(Self {
             applies_to: (source_start, source_start + range_length - 1),
             offset: destination_start - source_start,
@@ -171,7 +214,6 @@ type Range = (isize, isize); // (start, end)
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
     use super::*;
 
     #[test]
@@ -187,6 +229,30 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn bad_transforms_are_errors() {
+        let input = "seeds: 79 14\n\nalpha-to-beta\n50 98 2\n52 x 48".to_string();
+        let error = Error::parse(5, 4, "Bad transform source range start, invalid digit found in string");
+        assert_eq!(Almanac::from(&input).err(), Some(error));
+        let input = "seeds: 79 14\n\nalpha-to-beta\n50 98".to_string();
+        let error = Error::parse(4, 6, "No transform range length");
+        assert_eq!(Almanac::from(&input).err(), Some(error));
+    }
+
+    #[test]
+    fn bad_seed_ranges_are_errors() {
+        let input = "seeds: 79 14 55\n\nalpha-to-beta\n50 98 2".to_string();
+        assert!(Almanac::from(&input).is_ok());
+        let error = Error::parse(1, 16, "Seed range is missing its length");
+        assert_eq!(solve_day05_part2(input), Err(error));
+        let input = "seeds: 79 14 55 0\n\nalpha-to-beta\n50 98 2".to_string();
+        let error = Error::parse(1, 17, "Seed range length must be positive");
+        assert_eq!(solve_day05_part2(input), Err(error));
+        let input = format!("seeds: {} 2\n\nalpha-to-beta\n50 98 2", isize::MAX);
+        let error = Error::parse(1, 28, "Seed range is too long");
+        assert_eq!(solve_day05_part2(input), Err(error));
+    }
+
     #[test]
     fn splitting_ranges() -> Result<(), String> {
         assert_eq!(extract_range((1, 5), (6, 10)), (None, vec![(1, 5)]));
@@ -214,30 +280,20 @@ mod tests {
     }
 
     #[test]
-    fn solve_day05_part1_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
+    fn solve_day05_part1_on_crlf_example() -> Result<(), String> {
+        let windows = basics::read_input_file("examples/example.txt")?.replace('\n', "\r\n");
+        let input = basics::read_input_from(windows.as_bytes())?;
         assert_eq!(solve_day05_part1(input)?, "35");
         Ok(())
     }
 
     #[test]
-    fn solve_day05_part1_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day05_part1(input)?, "836040384");
-        Ok(())
+    fn solve_day05_part1_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART1)
     }
 
     #[test]
-    fn solve_day05_part2_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day05_part2(input)?, "46");
-        Ok(())
-    }
-
-    #[test]
-    fn solve_day05_part2_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day05_part2(input)?, "10834440");
-        Ok(())
+    fn solve_day05_part2_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART2)
     }
 }
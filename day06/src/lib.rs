@@ -1,5 +1,17 @@
-pub fn solve_day06_part1(input: String) -> Result<String, String> {
-    let records = RaceRecord::many_from(&input)?;
+use basics::{parsing, Error, Puzzle, Registry};
+
+pub const PART1: Puzzle = Puzzle::new(6, 1, TITLE, solve_day06_part1);
+pub const PART2: Puzzle = Puzzle::new(6, 2, TITLE, solve_day06_part2);
+const TITLE: &str = "Wait For It";
+
+/// Register the solvers of the day for tooling like the `aoc` runner.
+pub fn register(registry: &mut Registry) {
+    registry.register(PART1);
+    registry.register(PART2);
+}
+
+pub fn solve_day06_part1(input: String) -> Result<String, Error> {
+    let records = parsing(|| RaceRecord::many_from(&input))?;
     let multiplied = records
         .iter()
         .map(|r| r.count_ways_to_set_new_record())
@@ -7,7 +19,7 @@ pub fn solve_day06_part1(input: String) -> Result<String, String> {
     Ok(multiplied.to_string())
 }
 
-pub fn solve_day06_part2(input: String) -> Result<String, String> {
+pub fn solve_day06_part2(input: String) -> Result<String, Error> {
     let input = input.replace(" ", "");
     let input = input.replace(":", ": ");
     solve_day06_part1(input)
@@ -20,26 +32,26 @@ struct RaceRecord {
 }
 
 impl RaceRecord {
+    #[cfg(test)]
     fn new(race_duration: usize, record_distance: usize) -> Self {
         Self { race_duration, record_distance }
     }
 
-    fn many_from(input: &str) -> Result<Vec<Self>, String> {
+    fn many_from(input: &str) -> Result<Vec<Self>, Error> {
         let mut lines = input.lines();
+        let parse_number = |t: &str| t.parse::<usize>().map_err(|e| Error::parse_at(input, t, e.to_string()));
 
-        let mut time_row = lines.next().ok_or("Missing time row")?.split_whitespace();
+        let time_line = lines.next().ok_or_else(|| Error::parse_at_end(input, "Missing time row"))?;
+        let mut time_row = time_line.split_whitespace();
         time_row.next(); // discard "Time:"
-        let times = time_row
-            .map(|t| t.parse::<usize>().map_err(|e| e.to_string()))
-            .collect::<Result<Vec<_>, _>>()?;
+        let times = time_row.map(parse_number).collect::<Result<Vec<_>, _>>()?;
 
-        let mut distance_row = lines.next().ok_or("Missing distance row")?.split_whitespace();
+        let distance_line = lines.next().ok_or_else(|| Error::parse_at_end(input, "Missing distance row"))?;
+        let mut distance_row = distance_line.split_whitespace();
         distance_row.next(); // discard "Distance:"
-        let distances = distance_row
-            .map(|t| t.parse::<usize>().map_err(|e| e.to_string()))
-            .collect::<Result<Vec<_>, _>>()?;
+        let distances = distance_row.map(parse_number).collect::<Result<Vec<_>, _>>()?;
 
-        let records = times.into_iter().zip(distances.into_iter()).map(|(time, distance)| {
+        let records = times.into_iter().zip(distances).map(|(time, distance)| {
             Self { race_duration: time, record_distance: distance }
         }).collect::<Vec<_>>();
 
@@ -61,7 +73,7 @@ impl RaceRecord {
 }
 
 fn calculate_distance(total_ms: usize, hold_ms: usize) -> usize {
-    let diff_ms = if total_ms >= hold_ms { total_ms - hold_ms } else { 0 };
+    let diff_ms = total_ms.saturating_sub(hold_ms);
     diff_ms * hold_ms
 }
 
@@ -81,7 +93,6 @@ fn binary_search<F>(mut min: usize, mut max: usize, mut f: F) -> usize
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
     use super::*;
 
     #[test]
@@ -101,14 +112,14 @@ mod tests {
     #[test]
     fn does_holding_this_long_break_the_record() -> Result<(), String> {
         let record = RaceRecord::new(7, 9);
-        assert_eq!(record.do_we_break_the_record_by_holding_ms(0), false);
-        assert_eq!(record.do_we_break_the_record_by_holding_ms(1), false);
-        assert_eq!(record.do_we_break_the_record_by_holding_ms(2), true);
-        assert_eq!(record.do_we_break_the_record_by_holding_ms(3), true);
-        assert_eq!(record.do_we_break_the_record_by_holding_ms(4), true);
-        assert_eq!(record.do_we_break_the_record_by_holding_ms(5), true);
-        assert_eq!(record.do_we_break_the_record_by_holding_ms(6), false);
-        assert_eq!(record.do_we_break_the_record_by_holding_ms(7), false);
+        assert!(!record.do_we_break_the_record_by_holding_ms(0));
+        assert!(!record.do_we_break_the_record_by_holding_ms(1));
+        assert!(record.do_we_break_the_record_by_holding_ms(2));
+        assert!(record.do_we_break_the_record_by_holding_ms(3));
+        assert!(record.do_we_break_the_record_by_holding_ms(4));
+        assert!(record.do_we_break_the_record_by_holding_ms(5));
+        assert!(!record.do_we_break_the_record_by_holding_ms(6));
+        assert!(!record.do_we_break_the_record_by_holding_ms(7));
         Ok(())
     }
 
@@ -125,30 +136,12 @@ mod tests {
     }
 
     #[test]
-    fn solve_day06_part1_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day06_part1(input)?, "288");
-        Ok(())
-    }
-
-    #[test]
-    fn solve_day06_part1_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day06_part1(input)?, "128700");
-        Ok(())
+    fn solve_day06_part1_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART1)
     }
 
     #[test]
-    fn solve_day06_part2_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day06_part2(input)?, "71503");
-        Ok(())
-    }
-
-    #[test]
-    fn solve_day06_part2_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day06_part2(input)?, "39594072");
-        Ok(())
+    fn solve_day06_part2_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART2)
     }
 }
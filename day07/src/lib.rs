@@ -1,5 +1,17 @@
-pub fn solve_day07_part1(input: String) -> Result<String, String> {
-    let mut hands = Hand::many_from(&input, None)?;
+use basics::{parsing, Error, Puzzle, Registry};
+
+pub const PART1: Puzzle = Puzzle::new(7, 1, TITLE, solve_day07_part1);
+pub const PART2: Puzzle = Puzzle::new(7, 2, TITLE, solve_day07_part2);
+const TITLE: &str = "Camel Cards";
+
+/// Register the solvers of the day for tooling like the `aoc` runner.
+pub fn register(registry: &mut Registry) {
+    registry.register(PART1);
+    registry.register(PART2);
+}
+
+pub fn solve_day07_part1(input: String) -> Result<String, Error> {
+    let mut hands = parsing(|| Hand::many_from(&input, None))?;
     hands.sort();
     let winnings = hands
         .iter()
@@ -9,8 +21,8 @@ pub fn solve_day07_part1(input: String) -> Result<String, String> {
     Ok(winnings.to_string())
 }
 
-pub fn solve_day07_part2(input: String) -> Result<String, String> {
-    let mut hands = Hand::many_from(&input, Some('J'))?;
+pub fn solve_day07_part2(input: String) -> Result<String, Error> {
+    let mut hands = parsing(|| Hand::many_from(&input, Some('J')))?;
     hands.sort();
     let winnings = hands
         .iter()
@@ -27,34 +39,36 @@ struct Hand {
 }
 
 impl Hand {
-    fn from(input: &str, joker: Option<char>) -> Result<Hand, String> {
+    fn from(input: &str, joker: Option<char>) -> Result<Hand, Error> {
         let mut parts = input.split_whitespace();
-        let cards_str = parts.next().ok_or("No cards on line")?;
+        let cards_str = parts.next().ok_or(Error::parse(1, 1, "No cards on line"))?;
         let joker_char = joker.unwrap_or('?');
         let cards = cards_str
-            .chars()
-            .map(|c| match c {
-                _ if c == joker_char => JOKER_INDEX,  // joker is the lowest >:(
+            .char_indices()
+            .map(|(i, c)| match c {
+                _ if c == joker_char => Ok(JOKER_INDEX),  // joker is the lowest >:(
                 _ if c.to_digit(10)
-                    .and_then(|n| if n >= 2 && n <= 9 { Some(n) } else { None })
-                    .is_some() => c.to_digit(10).unwrap() as u8,
-                'T' => 10,
-                'J' => 11,
-                'Q' => 12,
-                'K' => 13,
-                'A' => 14,
-                _ => panic!("Invalid card '{}'", c),
+                    .and_then(|n| if (2..=9).contains(&n) { Some(n) } else { None })
+                    .is_some() => Ok(c.to_digit(10).unwrap() as u8),
+                'T' => Ok(10),
+                'J' => Ok(11),
+                'Q' => Ok(12),
+                'K' => Ok(13),
+                'A' => Ok(14),
+                _ => Err(Error::parse_at(input, &cards_str[i..], format!("Invalid card '{}'", c))),
             })
-            .collect::<Vec<_>>();
-        let bid_str = parts.next().ok_or("No bid on line")?;
-        let bid = bid_str.parse::<usize>().map_err(|e| e.to_string())?;
+            .collect::<Result<Vec<_>, _>>()?;
+        let bid_str = parts.next().ok_or_else(|| Error::parse_at_end(input, "No bid on line"))?;
+        let bid = bid_str
+            .parse::<usize>()
+            .map_err(|e| Error::parse_at(input, bid_str, format!("Bad bid, {}", e)))?;
         Ok(Hand { cards, bid })
     }
 
-    fn many_from(input: &str, joker: Option<char>) -> Result<Vec<Hand>, String> {
+    fn many_from(input: &str, joker: Option<char>) -> Result<Vec<Hand>, Error> {
         let hands = input
             .lines()
-            .map(|line| Hand::from(line, joker))
+            .map(|line| Hand::from(line, joker).map_err(|e| e.within(input, line)))
             .collect::<Result<Vec<_>, _>>()?;
         Ok(hands)
     }
@@ -143,7 +157,6 @@ const HIGH_CARD: u8 = 1;
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
     use super::*;
 
     #[test]
@@ -177,6 +190,14 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn bad_hands_are_errors() {
+        let error = Error::parse(2, 3, "Invalid card 'X'");
+        assert_eq!(Hand::many_from("32T3K 765\nT5XJ5 684", None).err(), Some(error));
+        let error = Error::parse(2, 7, "Bad bid, invalid digit found in string");
+        assert_eq!(Hand::many_from("32T3K 765\nT55J5 6x4", None).err(), Some(error));
+    }
+
     #[test]
     fn ordering() -> Result<(), String> {
         assert!(Hand::from("AAAAA 0", None)? > Hand::from("KKKKK 0", None)?);
@@ -195,30 +216,12 @@ mod tests {
     }
 
     #[test]
-    fn solve_day07_part1_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day07_part1(input)?, "6440");
-        Ok(())
-    }
-
-    #[test]
-    fn solve_day07_part1_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day07_part1(input)?, "250120186");
-        Ok(())
+    fn solve_day07_part1_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART1)
     }
 
     #[test]
-    fn solve_day07_part2_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day07_part2(input)?, "5905");
-        Ok(())
-    }
-
-    #[test]
-    fn solve_day07_part2_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day07_part2(input)?, "250665248");
-        Ok(())
+    fn solve_day07_part2_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART2)
     }
 }
@@ -1,25 +1,36 @@
 use std::collections::HashMap;
 use std::ops::Deref;
+use basics::{parsing, Error, Puzzle, Registry};
 
-pub fn solve_day08_part1(input: String) -> Result<String, String> {
-    let (instructions, network) = parse_input(input)?;
+pub const PART1: Puzzle = Puzzle::new(8, 1, TITLE, solve_day08_part1);
+pub const PART2: Puzzle = Puzzle::new(8, 2, TITLE, solve_day08_part2);
+const TITLE: &str = "Haunted Wasteland";
+
+/// Register the solvers of the day for tooling like the `aoc` runner.
+pub fn register(registry: &mut Registry) {
+    registry.register(PART1);
+    registry.register(PART2);
+}
+
+pub fn solve_day08_part1(input: String) -> Result<String, Error> {
+    let (instructions, network) = parsing(|| parse_input(input))?;
     let start = "AAA".to_string();
     let goal = "ZZZ".to_string();
     let mut label = start;
     for (route_length, step) in instructions.iter().enumerate() {
         if label == goal { return Ok(route_length.to_string()); }
-        let node = network.get(&label).ok_or_else(|| format!("Unknown label: {}", label))?;
+        let node = network.get(&label).ok_or_else(|| unknown_label(&label))?;
         match step {
             'L' => label = node.left.clone(),
             'R' => label = node.right.clone(),
-            _ => return Err(format!("Invalid instruction step: {}", step)),
+            _ => unreachable!("but we validated the instructions!?"),
         }
     };
-    Err(format!("No path to {}", goal))
+    Err(Error::no_solution(format!("No path to {}", goal)))
 }
 
-pub fn solve_day08_part2(input: String) -> Result<String, String> {
-    let (instructions, network) = parse_input(input)?;
+pub fn solve_day08_part2(input: String) -> Result<String, Error> {
+    let (instructions, network) = parsing(|| parse_input(input))?;
 
     let lengths_when_route_begins_to_loop = network
         .keys()
@@ -27,31 +38,35 @@ pub fn solve_day08_part2(input: String) -> Result<String, String> {
         .map(|start_label| {
             let mut label = start_label;
             for (route_length, step) in instructions.iter().enumerate() {
-                if label.ends_with('Z') { return route_length; }
-                let node = network.get(label).unwrap_or_else(|| panic!("Unknown label: {}", label));
+                if label.ends_with('Z') { return Ok(route_length); }
+                let node = network.get(label).ok_or_else(|| unknown_label(label))?;
                 match step {
                     'L' => label = &node.left,
                     'R' => label = &node.right,
-                    _ => panic!("Invalid instruction character: {}", step),
+                    _ => unreachable!("but we validated the instructions!?"),
                 }
             };
-            unreachable!()
+            unreachable!("but the instructions repeat forever!?")
         })
-        .collect::<Vec<_>>();
+        .collect::<Result<Vec<_>, Error>>()?;
 
     let least_common_multiple = lengths_when_route_begins_to_loop.into_iter().fold(1, lcm);
     Ok(least_common_multiple.to_string())
 }
 
-fn parse_input(input: String) -> Result<(Instructions, Network), String> {
+fn parse_input(input: String) -> Result<(Instructions, Network), Error> {
     let mut parts = input.split("\n\n");
-    let instructions_str = parts.next().ok_or("No instructions in input")?;
-    let instructions = Instructions::from(instructions_str);
-    let network_str = parts.next().ok_or("No network in input")?;
-    let network = Network::from(network_str)?;
+    let instructions_str = parts.next().expect("but split always yields at least one part!?");
+    let instructions = Instructions::from(instructions_str)?;
+    let network_str = parts.next().ok_or_else(|| Error::parse_at_end(&input, "No network in input"))?;
+    let network = Network::from(network_str).map_err(|e| e.within(&input, network_str))?;
     Ok((instructions, network))
 }
 
+fn unknown_label(label: &str) -> Error {
+    Error::no_solution(format!("Unknown label: {}", label))
+}
+
 struct Instructions(Vec<char>);
 
 impl Deref for Instructions {
@@ -60,11 +75,18 @@ impl Deref for Instructions {
 }
 
 impl Instructions {
-    fn from(text: &str) -> Self {
+    fn from(text: &str) -> Result<Self, Error> {
+        if text.is_empty() {
+            return Err(Error::parse(1, 1, "No instructions in input"));
+        }
+        if let Some((i, step)) = text.char_indices().find(|(_, c)| *c != 'L' && *c != 'R') {
+            let message = format!("Invalid instruction step: {}", step);
+            return Err(Error::parse_at(text, &text[i..], message));
+        }
         let as_chars = text.chars().collect::<Vec<char>>();
-        Self(as_chars)
+        Ok(Self(as_chars))
     }
-    pub fn iter(&self) -> InstructionsIterator {
+    pub fn iter(&self) -> InstructionsIterator<'_> {
         // there is probably some shorthand for this in std::iter but 🤷
         InstructionsIterator { instructions: self, index: 0, length: self.len() }
     }
@@ -94,27 +116,35 @@ impl Deref for Network {
 }
 
 impl Network {
-    fn from(text: &str) -> Result<Self, String> {
+    fn from(text: &str) -> Result<Self, Error> {
         let mut nodes = HashMap::new();
 
         // create the edge references
         for line in text.lines() {
-            let mut parts = line.split(" = ");
-            let node_label = parts.next().ok_or("Invalid node line, no node label")?;
-
-            let edges_str = parts.next().ok_or("Invalid node line, no edges")?;
-            let mut edges = edges_str
-                .split(",")
-                .map(|e_str| e_str.trim_matches(|c| c == '(' || c == ')' || c == ' '));
-            let left_label = edges.next().ok_or("Invalid node line, no left edge label")?;
-            let right_label = edges.next().ok_or("Invalid node line, no right edge label")?;
-
-            let node = Node::new(left_label, right_label);
+            let (node_label, node) = Self::node_from(line).map_err(|e| e.within(text, line))?;
             nodes.insert(node_label.to_string(), node);
         }
 
         Ok(Self(nodes))
     }
+
+    fn node_from(line: &str) -> Result<(&str, Node), Error> {
+        let mut parts = line.split(" = ");
+        let node_label = parts.next().expect("but split always yields at least one part!?");
+
+        let edges_str = parts
+            .next()
+            .ok_or_else(|| Error::parse_at_end(line, "Invalid node line, no edges"))?;
+        let mut edges = edges_str
+            .split(",")
+            .map(|e_str| e_str.trim_matches(|c| c == '(' || c == ')' || c == ' '));
+        let left_label = edges.next().expect("but split always yields at least one part!?");
+        let right_label = edges
+            .next()
+            .ok_or_else(|| Error::parse_at_end(line, "Invalid node line, no right edge label"))?;
+
+        Ok((node_label, Node::new(left_label, right_label)))
+    }
 }
 
 struct Node {
@@ -152,41 +182,36 @@ fn lcm(a: usize, b: usize) -> usize {
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
     use super::*;
 
     #[test]
-    fn solve_day08_part1_on_example_rl() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example-rl.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day08_part1(input)?, "2");
-        Ok(())
+    fn bad_inputs_are_errors() {
+        let input = "LXR\n\nAAA = (BBB, BBB)".to_string();
+        let error = Error::parse(1, 2, "Invalid instruction step: X");
+        assert_eq!(solve_day08_part1(input), Err(error));
+        let input = "LLR\n\nAAA = (BBB, BBB)\nBBB = (AAA)".to_string();
+        let error = Error::parse(4, 12, "Invalid node line, no right edge label");
+        assert_eq!(solve_day08_part1(input), Err(error));
+        let input = "LLR\n\nAAA = (BBB, BBB)".to_string();
+        let error = Error::no_solution("Unknown label: BBB");
+        assert_eq!(solve_day08_part1(input), Err(error));
     }
 
     #[test]
-    fn solve_day08_part1_on_example_llr() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example-llr.txt").map_err(|e| e.to_string())?;
+    fn solve_day08_part1_on_crlf_example_llr() -> Result<(), String> {
+        let windows = basics::read_input_file("examples/example-llr.txt")?.replace('\n', "\r\n");
+        let input = basics::read_input_from(windows.as_bytes())?;
         assert_eq!(solve_day08_part1(input)?, "6");
         Ok(())
     }
 
     #[test]
-    fn solve_day08_part1_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day08_part1(input)?, "19241");
-        Ok(())
+    fn solve_day08_part1_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART1)
     }
 
     #[test]
-    fn solve_day08_part2_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example-two-paths.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day08_part2(input)?, "6");
-        Ok(())
-    }
-
-    #[test]
-    fn solve_day08_part2_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day08_part2(input)?, "9606140307013");
-        Ok(())
+    fn solve_day08_part2_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART2)
     }
 }
@@ -1,25 +1,37 @@
-pub fn solve_day09_part1(input: String) -> Result<String, String> {
-    let sum = input.lines().map(predict_next).sum::<isize>();
+use basics::{parsing, Error, Puzzle, Registry};
+
+pub const PART1: Puzzle = Puzzle::new(9, 1, TITLE, solve_day09_part1);
+pub const PART2: Puzzle = Puzzle::new(9, 2, TITLE, solve_day09_part2);
+const TITLE: &str = "Mirage Maintenance";
+
+/// Register the solvers of the day for tooling like the `aoc` runner.
+pub fn register(registry: &mut Registry) {
+    registry.register(PART1);
+    registry.register(PART2);
+}
+
+pub fn solve_day09_part1(input: String) -> Result<String, Error> {
+    let histories = parsing(|| parse_histories(&input))?;
+    let sum = histories.iter().map(|observations| predict_next(observations)).sum::<Result<isize, Error>>()?;
     Ok(sum.to_string())
 }
 
-pub fn solve_day09_part2(input: String) -> Result<String, String> {
-    let sum = input.lines().map(extrapolate_previous).sum::<isize>();
+pub fn solve_day09_part2(input: String) -> Result<String, Error> {
+    let histories = parsing(|| parse_histories(&input))?;
+    let sum = histories.iter().map(|observations| extrapolate_previous(observations)).sum::<Result<isize, Error>>()?;
     Ok(sum.to_string())
 }
 
-fn predict_next(input: &str) -> isize {
-    let observations = parse_observations(input);
-    let stack = diff_stack(&observations);
+fn predict_next(observations: &[isize]) -> Result<isize, Error> {
+    let stack = diff_stack(observations)?;
     let last_diffs = stack.iter().map(|v| *v.last().unwrap()).collect::<Vec<isize>>();
     let last_observation = *observations.last().unwrap();
     let prediction = last_diffs.iter().fold(last_observation, |acc, v| acc + v);
-    return prediction;
+    Ok(prediction)
 }
 
-fn extrapolate_previous(input: &str) -> isize {
-    let observations = parse_observations(input);
-    let stack = diff_stack(&observations);
+fn extrapolate_previous(observations: &[isize]) -> Result<isize, Error> {
+    let stack = diff_stack(observations)?;
     let mut first_values = stack
         .iter()
         .map(|v| *v.first().unwrap())
@@ -27,74 +39,70 @@ fn extrapolate_previous(input: &str) -> isize {
         .collect::<Vec<isize>>();
     first_values.push(*observations.first().unwrap());
     let extrapolation = first_values.iter().fold(0, |acc, v| v - acc);
-    return extrapolation;
+    Ok(extrapolation)
+}
+
+fn parse_histories(input: &str) -> Result<Vec<Vec<isize>>, Error> {
+    input
+        .lines()
+        .map(|line| parse_observations(line).map_err(|e| e.within(input, line)))
+        .collect()
 }
 
-fn parse_observations(input: &str) -> Vec<isize> {
+fn parse_observations(input: &str) -> Result<Vec<isize>, Error> {
     input
         .split_whitespace()
-        .map(|s| s.parse::<isize>().unwrap())
+        .map(|s| s.parse::<isize>().map_err(|e| Error::parse_at(input, s, e.to_string())))
         .collect()
 }
 
-fn diff_stack(observations: &Vec<isize>) -> Vec<Vec<isize>> {
+fn diff_stack(observations: &[isize]) -> Result<Vec<Vec<isize>>, Error> {
     let mut stack: Vec<Vec<_>> = vec![];
-    let mut diffs = observations.clone();
+    let mut diffs = observations.to_vec();
     loop {
         diffs = diffs.windows(2).map(|pair| pair[1] - pair[0]).collect::<Vec<_>>();
         stack.push(diffs.clone());
-        if diffs.is_empty() { panic!("no zero diff in stack"); }
+        if diffs.is_empty() { return Err(Error::no_solution("No zero diff in stack")); }
         if diffs.iter().all(|&diff| diff == 0) { break; }
     }
-    stack
+    Ok(stack)
 }
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
     use super::*;
 
     #[test]
     fn predicting_example_lines() -> Result<(), String> {
-        assert_eq!(predict_next("0 3 6 9 12 15"), 18);
-        assert_eq!(predict_next("1 3 6 10 15 21"), 28);
-        assert_eq!(predict_next("10 13 16 21 30 45"), 68);
+        assert_eq!(predict_next(&parse_observations("0 3 6 9 12 15")?)?, 18);
+        assert_eq!(predict_next(&parse_observations("1 3 6 10 15 21")?)?, 28);
+        assert_eq!(predict_next(&parse_observations("10 13 16 21 30 45")?)?, 68);
         Ok(())
     }
 
     #[test]
-    fn solve_day09_part1_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day09_part1(input)?, "114");
-        Ok(())
-    }
-
-    #[test]
-    fn solve_day09_part1_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day09_part1(input)?, "1898776583");
-        Ok(())
+    fn bad_observations_are_errors() {
+        let error = Error::parse(2, 3, "invalid digit found in string");
+        assert_eq!(solve_day09_part1("0 3 6\n1 3x 6".to_string()), Err(error));
+        let error = Error::no_solution("No zero diff in stack");
+        assert_eq!(solve_day09_part1("0 3 6\n1 3 7".to_string()), Err(error));
     }
 
     #[test]
     fn extrapolating_example_lines() -> Result<(), String> {
-        assert_eq!(extrapolate_previous("0 3 6 9 12 15"), -3);
-        assert_eq!(extrapolate_previous("1 3 6 10 15 21"), 0);
-        assert_eq!(extrapolate_previous("10 13 16 21 30 45"), 5);
+        assert_eq!(extrapolate_previous(&parse_observations("0 3 6 9 12 15")?)?, -3);
+        assert_eq!(extrapolate_previous(&parse_observations("1 3 6 10 15 21")?)?, 0);
+        assert_eq!(extrapolate_previous(&parse_observations("10 13 16 21 30 45")?)?, 5);
         Ok(())
     }
 
     #[test]
-    fn solve_day09_part2_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day09_part2(input)?, "2");
-        Ok(())
+    fn solve_day09_part1_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART1)
     }
 
     #[test]
-    fn solve_day09_part2_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day09_part2(input)?, "1100");
-        Ok(())
+    fn solve_day09_part2_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART2)
     }
 }
@@ -1,135 +1,81 @@
-use std::collections::HashSet;
+use basics::{parsing, Error, Puzzle, Registry};
+use grid::{Direction, Grid, Point, Polygon};
 
-pub fn solve_day10_part1(input: String) -> Result<String, String> {
-    let sketch = Sketch::from_input(input);
-    let route_length = sketch.get_route().len();
-    Ok((route_length / 2).to_string())
+pub const PART1: Puzzle = Puzzle::new(10, 1, TITLE, solve_day10_part1);
+pub const PART2: Puzzle = Puzzle::new(10, 2, TITLE, solve_day10_part2);
+const TITLE: &str = "Pipe Maze";
+
+/// Register the solvers of the day for tooling like the `aoc` runner.
+pub fn register(registry: &mut Registry) {
+    registry.register(PART1);
+    registry.register(PART2);
 }
 
-pub fn solve_day10_part2(input: String) -> Result<String, String> {
-    let sketch = Sketch::from_input(input);
-    let enclosed_points = sketch.get_enclosed_points();
-    Ok(enclosed_points.len().to_string())
+pub fn solve_day10_part1(input: String) -> Result<String, Error> {
+    let sketch = parsing(|| Sketch::from_input(&input))?;
+    let route_length = sketch.get_route()?.len();
+    Ok((route_length / 2).to_string())
 }
 
-type Point = (usize, usize); // (x, y)
+pub fn solve_day10_part2(input: String) -> Result<String, Error> {
+    let sketch = parsing(|| Sketch::from_input(&input))?;
+    Ok(sketch.get_enclosed_count()?.to_string())
+}
 
 #[derive(Debug)]
-struct Sketch(Vec<Vec<char>>);
+struct Sketch(Grid<char>);
 
 impl Sketch {
-    fn from_input(input: String) -> Self {
-        let sketch = input.lines().map(|l| l.chars().collect::<Vec<_>>()).collect();
-        Self(sketch)
-    }
-
-    fn get_side_point(&self, point: &Point, entry: &Direction, side: &Side) -> Option<Point> {
-        let direction = entry.turn_to(side);
-        self.neighbors(point)
-            .into_iter()
-            .find(|(dir, _)| dir == &direction)
-            .map(|(_, p)| p)
+    fn from_input(input: &str) -> Result<Self, Error> {
+        let grid = Grid::parse(input, |c| match "|-LJ7F.S".contains(c) {
+            true => Ok(c),
+            false => Err(format!("Invalid tile '{}'", c)),
+        })?;
+        Ok(Self(grid))
     }
 
     fn get_content(&self, point: &Point) -> Option<char> {
-        let (x, y) = *point;
-        self.0.get(y).and_then(|row| row.get(x)).cloned()
+        self.0.get(*point).copied()
     }
 
     fn find(&self, c: &char) -> Option<Point> {
-        for (y, row) in self.0.iter().enumerate() {
-            for (x, cell) in row.iter().enumerate() {
-                if cell == c {
-                    return Some((x, y));
-                }
-            }
-        }
-        None
+        self.0.position(|cell| cell == c)
     }
 
-    fn get_enclosed_points(&self) -> HashSet<Point> {
-        let route = self.get_route();
-
-        let mut full_route = route.clone();
-        full_route.push(full_route[0].clone());
-
-        // keep track of turns to determine which side is the interior
-        let degrees = full_route
-            .windows(2)
-            .map(|w| {
-                let (dir1, _) = &w[0];
-                let (dir2, _) = &w[1];
-                let degrees = match (dir1, dir2) {
-                    (Direction::Up, Direction::Right) => 90,
-                    (Direction::Up, Direction::Left) => -90,
-                    (Direction::Right, Direction::Up) => -90,
-                    (Direction::Right, Direction::Down) => 90,
-                    (Direction::Down, Direction::Right) => -90,
-                    (Direction::Down, Direction::Left) => 90,
-                    (Direction::Left, Direction::Up) => 90,
-                    (Direction::Left, Direction::Down) => -90,
-                    _ => 0,
-                };
-                degrees
-            })
-            .sum::<isize>();
-        let sideness = if degrees == 360 {
-            Side::Right
-        } else if degrees == -360 {
-            Side::Left
-        } else {
-            panic!("Invalid route turns degree sum: {}", degrees);
-        };
-
-        let route_points = route.iter().map(|(_, p)| p).collect::<HashSet<_>>();
-
-        let mut enclosed_points = route
-            .iter()
-            .map(|(entry, point)| self.get_side_point(point, entry, &sideness))
-            .filter_map(|to_some_point| to_some_point)
-            .filter(|p| !route_points.contains(p))
-            .collect::<HashSet<Point>>();
-
-        let mut to_check = enclosed_points.clone();
-        while !to_check.is_empty() {
-            let mut next_to_check = HashSet::new();
-            for point in to_check {
-                for (_, neighbor) in self.neighbors(&point) {
-                    if enclosed_points.contains(&neighbor) || route_points.contains(&neighbor) {
-                        continue;
-                    }
-                    next_to_check.insert(neighbor);
-                }
-            }
-            enclosed_points.extend(next_to_check.clone());
-            to_check = next_to_check;
-        }
-
-        enclosed_points
+    /// Count the enclosed points from the area of the route, i.e. with the shoelace formula
+    /// and Pick's theorem.
+    fn get_enclosed_count(&self) -> Result<u128, Error> {
+        let route = self.get_route()?;
+        let vertices = route.iter().map(|(_, (x, y))| (*x as i64, *y as i64)).collect::<Vec<_>>();
+        Polygon::new(vertices)
+            .interior_points()
+            .ok_or_else(|| Error::no_solution("The route retraces itself"))
     }
 
-    fn get_route(&self) -> Vec<(Direction, Point)> {
-        let start = self.find(&'S').unwrap_or_else(|| panic!("No starting point found"));
+    fn get_route(&self) -> Result<Vec<(Direction, Point)>, Error> {
+        let start = self.find(&'S').ok_or_else(|| Error::no_solution("No starting point found"))?;
         let mut route = vec![];
-        let mut next = self.valid_neighbors(&start).first().unwrap().clone();
+        let mut next = *self.valid_neighbors(&start)
+            .first()
+            .ok_or_else(|| Error::no_solution("No pipe connects to the starting point"))?;
         loop {
-            route.push(next.clone());
+            route.push(next);
             if next.1 == start { break; }
-            next = self.valid_neighbor_from_entry(&next.1, &next.0.opposite());
+            next = self.valid_neighbor_from_entry(&next.1, &next.0.opposite())?;
         }
-        route
+        Ok(route)
     }
 
-    fn valid_neighbor_from_entry(&self, point: &Point, entry: &Direction) -> (Direction, Point) {
+    fn valid_neighbor_from_entry(&self, point: &Point, entry: &Direction) -> Result<(Direction, Point), Error> {
         self.valid_neighbors(point)
             .into_iter()
             .find(|(direction, _)| direction != entry)
-            .unwrap()
+            .ok_or_else(|| Error::no_solution(format!("Dead end in the pipes at {:?}", point)))
     }
 
     fn valid_neighbors(&self, point: &Point) -> Vec<(Direction, Point)> {
-        let source = self.get_content(point).unwrap_or_else(|| panic!("Nothing at {:?}", point));
-        let result = self.neighbors(point)
+        let Some(source) = self.get_content(point) else { return vec![]; }; // nothing at the point
+        self.neighbors(point)
             .into_iter()
             .filter(|(dir, _)| { // filter out directions not valid from the source
                 match dir {
@@ -148,70 +94,27 @@ impl Sketch {
                     Direction::Left => dest == '-' || dest == 'F' || dest == 'L' || dest == 'S',
                 }
             })
-            .collect::<Vec<_>>();
-        result
+            .collect::<Vec<_>>()
     }
 
     fn neighbors(&self, point: &Point) -> Vec<(Direction, Point)> {
-        let (x, y) = *point;
-        let mut neighbors = vec![];
-        if x > 0 {
-            neighbors.push((Direction::Left, (x - 1, y)));
-        }
-        if y > 0 {
-            neighbors.push((Direction::Up, (x, y - 1)));
-        }
-        if x < self.0[0].len() - 1 {
-            neighbors.push((Direction::Right, (x + 1, y)));
-        }
-        if y < self.0.len() - 1 {
-            neighbors.push((Direction::Down, (x, y + 1)));
-        }
-        neighbors
-    }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-enum Direction { Up, Right, Down, Left }
-
-impl Direction {
-    fn opposite(&self) -> Self {
-        match self {
-            Direction::Up => Direction::Down,
-            Direction::Right => Direction::Left,
-            Direction::Down => Direction::Up,
-            Direction::Left => Direction::Right,
-        }
-    }
-    fn turn_to(&self, sideness: &Side) -> Direction {
-        match (self, sideness) {
-            (Direction::Up, Side::Left) => Direction::Left,
-            (Direction::Up, Side::Right) => Direction::Right,
-            (Direction::Right, Side::Left) => Direction::Up,
-            (Direction::Right, Side::Right) => Direction::Down,
-            (Direction::Down, Side::Left) => Direction::Right,
-            (Direction::Down, Side::Right) => Direction::Left,
-            (Direction::Left, Side::Left) => Direction::Down,
-            (Direction::Left, Side::Right) => Direction::Up,
-        }
+        // the order matters, the route starts towards the first pipe connecting to the start
+        [Direction::Left, Direction::Up, Direction::Right, Direction::Down]
+            .into_iter()
+            .filter_map(|direction| self.0.step(*point, direction).map(|p| (direction, p)))
+            .collect()
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-enum Side {
-    Left,
-    Right,
-}
-
 #[cfg(test)]
 mod tests {
-    use std::fs;
+    use std::collections::HashSet;
     use super::*;
 
     #[test]
     fn sketch_from_simple_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example-simple.txt").map_err(|e| e.to_string())?;
-        let sketch = Sketch::from_input(input);
+        let input = basics::read_input_file("examples/example-simple.txt")?;
+        let sketch = Sketch::from_input(&input)?;
         assert_eq!(sketch.get_content(&(99, 99)), None);
         assert_eq!(sketch.get_content(&(1, 1)), Some('S'));
         assert_eq!(sketch.get_content(&(3, 1)), Some('7'));
@@ -223,61 +126,85 @@ mod tests {
         assert_eq!(sketch.neighbors(&start).len(), 4);
         assert_eq!(sketch.valid_neighbors(&start).len(), 2);
 
-        assert_eq!(sketch.get_route().len(), 8);
+        assert_eq!(sketch.get_route()?.len(), 8);
         Ok(())
     }
 
+    /// Flood fill the points enclosed by the route, starting from the interior side of it,
+    /// to cross-check counting them from the area.
+    fn flood_fill_enclosed(sketch: &Sketch) -> Result<HashSet<Point>, Error> {
+        let route = sketch.get_route()?;
+
+        // the interior is on the side the route turns towards all in all, on the screen
+        // right turns are clockwise
+        let headings = route.iter().map(|(direction, _)| *direction).collect::<Vec<_>>();
+        let next_headings = headings.iter().skip(1).chain(headings.first());
+        let right_turns = headings.iter().zip(next_headings.clone()).fold(0, |turns, (from, to)| {
+            if *to == from.turn_right() { turns + 1 } else if *to == from.turn_left() { turns - 1 } else { turns }
+        });
+        let to_interior = match right_turns {
+            4 => Direction::turn_right,
+            -4 => Direction::turn_left,
+            _ => return Err(Error::no_solution(format!("Route turns {} times right", right_turns))),
+        };
+
+        // at the corners, both the heading in and the heading out have an interior side
+        let route_points = route.iter().map(|(_, point)| *point).collect::<HashSet<_>>();
+        let mut to_check = route
+            .iter()
+            .zip(next_headings)
+            .flat_map(|((heading_in, point), heading_out)| {
+                [heading_in, heading_out].map(|heading| sketch.0.step(*point, to_interior(heading)))
+            })
+            .flatten()
+            .collect::<Vec<_>>();
+        let mut enclosed_points = HashSet::new();
+        while let Some(point) = to_check.pop() {
+            if route_points.contains(&point) || !enclosed_points.insert(point) {
+                continue;
+            }
+            to_check.extend(sketch.0.neighbors4(point));
+        }
+        Ok(enclosed_points)
+    }
+
     #[test]
-    fn solve_day10_part1_on_examples() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example-simple.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day10_part1(input)?, "4");
-        let input = fs::read_to_string("examples/example-large-simple.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day10_part1(input)?, "12");
-        let input = fs::read_to_string("examples/example-messy.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day10_part1(input)?, "4");
-        let input = fs::read_to_string("examples/example-complex.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day10_part1(input)?, "8");
-        let input = fs::read_to_string("examples/example-alternative.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day10_part1(input)?, "3022");
+    fn enclosed_points_by_area_and_by_flood_fill() -> Result<(), String> {
+        let mut paths = std::fs::read_dir("examples")
+            .map_err(|e| e.to_string())?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|extension| extension == "txt"))
+            .collect::<Vec<_>>();
+        paths.sort();
+        for path in paths {
+            let input = basics::read_input_file(&path)?;
+            let sketch = Sketch::from_input(&input)?;
+            let flood_filled = flood_fill_enclosed(&sketch)?.len() as u128;
+            assert_eq!(sketch.get_enclosed_count()?, flood_filled, "{}", path.display());
+        }
         Ok(())
     }
 
     #[test]
-    fn solve_day10_part1_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day10_part1(input)?, "6823");
-        Ok(())
+    fn bad_sketches_are_errors() {
+        let input = ".....\n.S-7.\n.|x|.".to_string();
+        let error = Error::parse(3, 3, "Invalid tile 'x'");
+        assert_eq!(solve_day10_part1(input), Err(error));
+        let input = ".....\n.F-7.\n.L-J.".to_string();
+        let error = Error::no_solution("No starting point found");
+        assert_eq!(solve_day10_part1(input), Err(error));
+        let input = ".....\n.S-7.\n.|...".to_string();
+        let error = Error::no_solution("Dead end in the pipes at (3, 1)");
+        assert_eq!(solve_day10_part1(input), Err(error));
     }
 
     #[test]
-    fn solve_day10_part2_on_examples() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example-simple.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day10_part2(input)?, "1");
-        let input = fs::read_to_string("examples/example-large-simple.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day10_part2(input)?, "25");
-        let input = fs::read_to_string("examples/example-messy.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day10_part2(input)?, "1");
-        let input = fs::read_to_string("examples/example-complex.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day10_part2(input)?, "1");
-        let input = fs::read_to_string("examples/example-enclosed.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day10_part2(input)?, "4");
-        let input = fs::read_to_string("examples/example-enclosed-with-no-gap.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day10_part2(input)?, "4");
-        let input = fs::read_to_string("examples/example-random-bits.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day10_part2(input)?, "8");
-        let input = fs::read_to_string("examples/example-junk.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day10_part2(input)?, "10");
-        let input = fs::read_to_string("examples/example-alternative.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day10_part2(input)?, "0");
-        Ok(())
+    fn solve_day10_part1_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART1)
     }
 
     #[test]
-    fn solve_day10_part2_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day10_part2(input)?, "413"); // this is what I get, but...
-        // assert_eq!(solve_day10_part2(input)?, "415"); // the right answer with trial-and-error 🤷
-        // so probably I'm not handling some edge case correctly, I'll see if I get back to this
-        Ok(())
+    fn solve_day10_part2_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART2)
     }
 }
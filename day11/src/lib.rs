@@ -1,15 +1,26 @@
 use std::collections::HashSet;
 
 use itertools::Itertools;
+use basics::{parsing, Error, Puzzle, Registry};
 
-pub fn solve_day11_part1(input: String) -> Result<String, String> {
-    let mut image = SpaceImage::from(input);
+pub const PART1: Puzzle = Puzzle::new(11, 1, TITLE, solve_day11_part1);
+pub const PART2: Puzzle = Puzzle::new(11, 2, TITLE, solve_day11_part2);
+const TITLE: &str = "Cosmic Expansion";
+
+/// Register the solvers of the day for tooling like the `aoc` runner.
+pub fn register(registry: &mut Registry) {
+    registry.register(PART1);
+    registry.register(PART2);
+}
+
+pub fn solve_day11_part1(input: String) -> Result<String, Error> {
+    let mut image = parsing(|| SpaceImage::from(&input))?;
     image.light_travel_tick(1)?;
     Ok(image.galaxy_distance_sum()?.to_string())
 }
 
-pub fn solve_day11_part2(input: String) -> Result<String, String> {
-    let mut image = SpaceImage::from(input);
+pub fn solve_day11_part2(input: String) -> Result<String, Error> {
+    let mut image = parsing(|| SpaceImage::from(&input))?;
     image.light_travel_tick(1000000 - 1)?;
     Ok(image.galaxy_distance_sum()?.to_string())
 }
@@ -22,31 +33,37 @@ struct SpaceImage {
 }
 
 impl SpaceImage {
+    #[cfg(test)]
     fn new(galaxies: HashSet<Point>) -> Self {
         SpaceImage { galaxies }
     }
 
-    fn from(input: String) -> Self {
+    fn from(input: &str) -> Result<Self, Error> {
         let mut galaxies = HashSet::new();
         for (y, line) in input.lines().enumerate() {
-            for (x, column) in line.chars().enumerate() {
-                if column == '#' {
-                    galaxies.insert((x as isize, y as isize));
+            for (x, (i, column)) in line.char_indices().enumerate() {
+                match column {
+                    '#' => { galaxies.insert((x as isize, y as isize)); }
+                    '.' => {}
+                    _ => {
+                        let message = format!("Invalid pixel '{}'", column);
+                        return Err(Error::parse_at(input, &line[i..], message));
+                    }
                 }
             }
         }
-        SpaceImage { galaxies }
+        Ok(SpaceImage { galaxies })
     }
 
-    fn last_column(&self) -> Result<isize, String> {
-        self.galaxies.iter().map(|(x, _)| x).max().cloned().ok_or("No galaxies".to_string())
+    fn last_column(&self) -> Result<isize, Error> {
+        self.galaxies.iter().map(|(x, _)| x).max().cloned().ok_or_else(|| Error::no_solution("No galaxies"))
     }
 
-    fn last_row(&self) -> Result<isize, String> {
-        self.galaxies.iter().map(|(_, y)| y).max().cloned().ok_or("No galaxies".to_string())
+    fn last_row(&self) -> Result<isize, Error> {
+        self.galaxies.iter().map(|(_, y)| y).max().cloned().ok_or_else(|| Error::no_solution("No galaxies"))
     }
 
-    fn void_rows(&self) -> Result<Vec<isize>, String> {
+    fn void_rows(&self) -> Result<Vec<isize>, Error> {
         let mut void_rows = Vec::new();
         for y in (0..=self.last_row()?).rev() {
             if !self.galaxies.iter().any(|(_, _y)| _y == &y) {
@@ -56,7 +73,7 @@ impl SpaceImage {
         Ok(void_rows)
     }
 
-    fn void_columns(&self) -> Result<Vec<isize>, String> {
+    fn void_columns(&self) -> Result<Vec<isize>, Error> {
         let mut void_columns = Vec::new();
         for x in (0..=self.last_column()?).rev() {
             if !self.galaxies.iter().any(|(_x, _)| _x == &x) {
@@ -66,7 +83,7 @@ impl SpaceImage {
         Ok(void_columns)
     }
 
-    fn light_travel_tick(&mut self, light_years: isize) -> Result<(), String> {
+    fn light_travel_tick(&mut self, light_years: isize) -> Result<(), Error> {
         let void_rows = self.void_rows()?;
         for void_y in void_rows {
             self.galaxies = self.galaxies
@@ -84,12 +101,12 @@ impl SpaceImage {
         Ok(())
     }
 
-    fn galaxy_combinations(&self) -> Result<Vec<Vec<&Point>>, String> {
+    fn galaxy_combinations(&self) -> Result<Vec<Vec<&Point>>, Error> {
         let combinations = self.galaxies.iter().combinations(2).collect::<Vec<_>>();
         Ok(combinations)
     }
 
-    fn galaxy_distance_sum(&self) -> Result<isize, String> {
+    fn galaxy_distance_sum(&self) -> Result<isize, Error> {
         let sum_of_shortest_paths = self
             .galaxy_combinations()?
             .iter()
@@ -105,7 +122,6 @@ fn distance(p1: &Point, p2: &Point) -> isize {
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
     use super::*;
 
     #[test]
@@ -122,23 +138,19 @@ mod tests {
     }
 
     #[test]
-    fn solve_day11_part1_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day11_part1(input)?, "374");
-        Ok(())
-    }
-
-    #[test]
-    fn solve_day11_part1_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day11_part1(input)?, "9639160");
-        Ok(())
+    fn bad_images_are_errors() {
+        let input = "#..\n.?#".to_string();
+        let error = Error::parse(2, 2, "Invalid pixel '?'");
+        assert_eq!(solve_day11_part1(input), Err(error));
+        let input = "...\n...".to_string();
+        let error = Error::no_solution("No galaxies");
+        assert_eq!(solve_day11_part1(input), Err(error));
     }
 
     #[test]
     fn solve_day11_part2_on_example_10() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        let mut image = SpaceImage::from(input);
+        let input = basics::read_input_file("examples/example.txt")?;
+        let mut image = SpaceImage::from(&input)?;
         image.light_travel_tick(10 - 1)?;
         assert_eq!(image.galaxy_distance_sum()?, 1030);
         Ok(())
@@ -146,17 +158,20 @@ mod tests {
 
     #[test]
     fn solve_day11_part2_on_example_100() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        let mut image = SpaceImage::from(input);
+        let input = basics::read_input_file("examples/example.txt")?;
+        let mut image = SpaceImage::from(&input)?;
         image.light_travel_tick(100 - 1)?;
         assert_eq!(image.galaxy_distance_sum()?, 8410);
         Ok(())
     }
 
     #[test]
-    fn solve_day11_part2_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day11_part2(input)?, "752936133304");
-        Ok(())
+    fn solve_day11_part1_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART1)
+    }
+
+    #[test]
+    fn solve_day11_part2_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART2)
     }
 }
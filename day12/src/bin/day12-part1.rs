@@ -1,6 +1,6 @@
-fn main() -> Result<(), String> {
-    let input = basics::read_input()?;
-    let result = day12::solve_day12_part1(input)?;
-    println!("{}", result);
-    Ok(())
+use std::process::ExitCode;
+use day12::Arranging;
+
+fn main() -> ExitCode {
+    basics::run(Arranging::new())
 }
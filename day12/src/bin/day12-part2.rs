@@ -1,6 +1,6 @@
-fn main() -> Result<(), String> {
-    let input = basics::read_input()?;
-    let result = day12::solve_day12_part2(input)?;
-    println!("{}", result);
-    Ok(())
+use std::process::ExitCode;
+use day12::{Unfolding, UNFOLD_FACTOR};
+
+fn main() -> ExitCode {
+    basics::run(Unfolding::new(UNFOLD_FACTOR))
 }
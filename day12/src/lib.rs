@@ -1,19 +1,193 @@
+use std::collections::HashMap;
+#[cfg(test)]
 use itertools::Itertools;
+use basics::{parsing, Error, Puzzle, Registry, Solver, SolverOption};
+
+pub const PART1: Puzzle = Puzzle::new(12, 1, TITLE, solve_day12_part1);
+pub const PART2: Puzzle = Puzzle::new(12, 2, TITLE, solve_day12_part2);
+const TITLE: &str = "Hot Springs";
+
+/// Register the solvers of the day for tooling like the `aoc` runner.
+pub fn register(registry: &mut Registry) {
+    registry.register(Arranging::new());
+    registry.register(Unfolding::new(UNFOLD_FACTOR));
+}
+
+pub fn solve_day12_part1(input: String) -> Result<String, Error> {
+    arrangement_sum(&input, 1)
+}
+
+pub fn solve_day12_part2(input: String) -> Result<String, Error> {
+    arrangement_sum(&input, UNFOLD_FACTOR)
+}
+
+/// How many copies of itself a row really is in part 2.
+pub const UNFOLD_FACTOR: usize = 5;
+
+/// The part 2 solver with the rows unfolded by any factor, to see how the counts grow,
+/// e.g. with `--factor 20000`.
+#[derive(Debug, Clone, Copy)]
+pub struct Unfolding {
+    factor: usize,
+}
+
+impl Unfolding {
+    pub fn new(factor: usize) -> Self {
+        Self { factor }
+    }
+}
+
+impl Solver for Unfolding {
+    fn day(&self) -> u8 { 12 }
+    fn part(&self) -> u8 { 2 }
+    fn title(&self) -> &str { TITLE }
+    fn solve(&self, input: String) -> Result<String, Error> { arrangement_sum(&input, self.factor) }
+    fn checks_answers(&self) -> bool { self.factor == UNFOLD_FACTOR }
+    fn options(&self) -> &[SolverOption] { &[FACTOR_OPTION] }
+
+    fn with_options(&self, options: &[(String, String)]) -> Result<Box<dyn Solver>, Error> {
+        let mut factor = self.factor;
+        for (_, copies) in options {
+            factor = match copies.parse::<usize>() {
+                Ok(copies) if copies > 0 => copies,
+                _ => return Err(Error::usage(format!("Invalid --factor copies: {}", copies))),
+            };
+        }
+        Ok(Box::new(Self::new(factor)))
+    }
+}
+
+const FACTOR_OPTION: SolverOption = SolverOption {
+    name: "--factor",
+    value: "<copies>",
+    help: "unfold the rows into this many copies instead of 5,\nnot checking the answers when it differs",
+};
 
-pub fn solve_day12_part1(input: String) -> Result<String, String> {
-    let rows = input
-        .lines()
-        .map(|line| SpringRow::from(line))
-        .collect::<Result<Vec<_>, String>>()?;
+fn arrangement_sum(input: &str, factor: usize) -> Result<String, Error> {
+    let rows = parsing(|| {
+        input
+            .lines()
+            .map(|line| SpringRow::from(line).map_err(|e| e.within(input, line)))
+            .collect::<Result<Vec<_>, Error>>()
+    })?;
     let valid_arrangement_sum = rows
         .iter()
-        .map(|row| row.valid_permutations().unwrap().len())
-        .sum::<usize>();
+        .map(|row| row.unfold(factor).arrangement_count())
+        .try_fold(0_u128, |sum, count| match count {
+            u128::MAX => None, // saturated
+            _ => sum.checked_add(count),
+        })
+        .ok_or_else(|| Error::no_solution(format!("Too many arrangements to count with factor {}", factor)))?;
     Ok(valid_arrangement_sum.to_string())
 }
 
-pub fn solve_day12_part2(input: String) -> Result<String, String> {
-    Ok(input)
+/// The part 1 solver listing the valid arrangements of each row rather than counting them,
+/// all of them with `--list all` or a random sample with e.g. `--list 5 --seed 7`.
+#[derive(Debug, Clone, Copy)]
+pub struct Arranging {
+    listing: Option<Listing>,
+    seed: u64,
+}
+
+/// Which valid arrangements `--list` lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Listing {
+    All,
+    /// This many picked at random, each arrangement as likely as any other.
+    Sample(usize),
+}
+
+impl Arranging {
+    pub fn new() -> Self {
+        Self { listing: None, seed: 1 }
+    }
+}
+
+impl Default for Arranging {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Solver for Arranging {
+    fn day(&self) -> u8 { 12 }
+    fn part(&self) -> u8 { 1 }
+    fn title(&self) -> &str { TITLE }
+
+    fn solve(&self, input: String) -> Result<String, Error> {
+        match self.listing {
+            Some(listing) => list_arrangements(&input, listing, self.seed),
+            None => solve_day12_part1(input),
+        }
+    }
+
+    fn checks_answers(&self) -> bool { self.listing.is_none() }
+    fn options(&self) -> &[SolverOption] { &[LIST_OPTION, SEED_OPTION] }
+
+    fn with_options(&self, options: &[(String, String)]) -> Result<Box<dyn Solver>, Error> {
+        let mut arranging = *self;
+        for (option, value) in options {
+            match option.as_str() {
+                "--seed" => {
+                    arranging.seed = value.parse::<u64>().map_err(|_| Error::usage(format!("Invalid --seed: {}", value)))?;
+                }
+                _ => {
+                    arranging.listing = Some(match value.as_str() {
+                        "all" => Listing::All,
+                        count => Listing::Sample(count.parse::<usize>().map_err(|_| {
+                            Error::usage(format!("Invalid --list arrangements: {}", count))
+                        })?),
+                    });
+                }
+            }
+        }
+        Ok(Box::new(arranging))
+    }
+}
+
+const LIST_OPTION: SolverOption = SolverOption {
+    name: "--list",
+    value: "<all | count>",
+    help: "list the valid arrangements of each row after it instead of\n\
+           counting them, all of them or that many picked at random",
+};
+
+const SEED_OPTION: SolverOption = SolverOption {
+    name: "--seed",
+    value: "<seed>",
+    help: "pick the same random arrangements for the same seed, 1 by default",
+};
+
+// the most arrangements listed, all of them in memory before they're shown
+const MAX_LISTED: u128 = 1_000_000;
+
+fn list_arrangements(input: &str, listing: Listing, seed: u64) -> Result<String, Error> {
+    let rows = parsing(|| {
+        input
+            .lines()
+            .map(|line| SpringRow::from(line).map(|row| (line, row)).map_err(|e| e.within(input, line)))
+            .collect::<Result<Vec<_>, Error>>()
+    })?;
+    let listed = rows.iter().fold(0_u128, |listed, (_, row)| {
+        listed.saturating_add(match listing {
+            Listing::All => row.arrangement_count(),
+            Listing::Sample(count) => count as u128,
+        })
+    });
+    if listed > MAX_LISTED {
+        let message = format!("Too many arrangements to list, {}, the most is {}", listed, MAX_LISTED);
+        return Err(Error::no_solution(message));
+    }
+    let mut lines = vec![];
+    for (line, row) in rows.iter() {
+        lines.push(line.to_string());
+        let arrangements = match listing {
+            Listing::All => row.arrangements().collect::<Vec<_>>(),
+            Listing::Sample(count) => row.sample_arrangements(count, seed),
+        };
+        lines.extend(arrangements.into_iter().map(|arrangement| format!("  {}", arrangement)));
+    }
+    Ok(lines.join("\n"))
 }
 
 #[derive(Debug)]
@@ -23,29 +197,92 @@ struct SpringRow {
 }
 
 impl SpringRow {
-    fn from(input: &str) -> Result<Self, String> {
+    fn from(input: &str) -> Result<Self, Error> {
         let mut parts = input.split_whitespace();
         let springs = parts
             .next()
-            .ok_or(format!("Row is missing springs: {input:?}"))?
-            .to_string();
+            .ok_or_else(|| Error::parse_at_end(input, "Row is missing springs"))?;
+        if let Some((i, c)) = springs.char_indices().find(|(_, c)| !".#?".contains(*c)) {
+            return Err(Error::parse_at(input, &springs[i..], format!("Invalid spring '{}'", c)));
+        }
         let damages = parts
             .next()
-            .ok_or(format!("Row is missing damaged groups: {input:?}"))?
+            .ok_or_else(|| Error::parse_at_end(input, "Row is missing damaged groups"))?
             .split(',')
-            .map(|s| s.parse::<usize>().map_err(|e| e.to_string()))
-            .collect::<Result<Vec<_>, String>>()?;
-        Ok(Self { springs, damages })
+            .map(|s| s.parse::<usize>().map_err(|e| Error::parse_at(input, s, e.to_string())))
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(Self { springs: springs.to_string(), damages })
+    }
+
+    /// The row as the given number of copies of itself separated by unknown springs,
+    /// e.g. as it really is in part 2 with "five copies of itself".
+    fn unfold(&self, factor: usize) -> Self {
+        Self {
+            springs: vec![self.springs.as_str(); factor].join("?"),
+            damages: self.damages.repeat(factor),
+        }
+    }
+
+    /// The number of ways to fill the unknowns that match the damaged groups,
+    /// counted spring by spring without building any of the arrangements.
+    /// The count saturates at `u128::MAX` rather than overflow, with large unfolding factors.
+    fn arrangement_count(&self) -> u128 {
+        Counter::new(self).count(START)
+    }
+
+    fn arrangements(&self) -> Arrangements {
+        let counter = Counter::new(self);
+        let stack = if counter.count(START) > 0 { vec![(START, None)] } else { vec![] };
+        Arrangements { counter, stack, filled: String::new() }
+    }
+
+    /// Pick valid arrangements at random, each one as likely as the others, by choosing each
+    /// unknown spring in proportion to the arrangement counts it leads to.
+    fn sample_arrangements(&self, sample_size: usize, seed: u64) -> Vec<String> {
+        let counter = Counter::new(self);
+        if counter.count(START) == 0 {
+            return vec![];
+        }
+        let mut random = Random::new(seed);
+        (0..sample_size)
+            .map(|_| {
+                let mut state = START;
+                let mut filled = String::new();
+                while !counter.is_done(state) {
+                    let choices = counter
+                        .transitions(state)
+                        .into_iter()
+                        .map(|(symbol, next)| (symbol, next, counter.count(next)))
+                        .collect::<Vec<_>>();
+                    let mut pick = random.below(choices.iter().map(|(_, _, count)| *count).fold(0, u128::saturating_add));
+                    let (symbol, next, _) = choices
+                        .into_iter()
+                        .find(|(_, _, count)| {
+                            let found = pick < *count;
+                            pick = pick.saturating_sub(*count);
+                            found
+                        })
+                        .expect("but the pick is below the total count!?");
+                    filled.push(symbol);
+                    state = next;
+                }
+                filled
+            })
+            .collect()
     }
+}
 
+// the brute force of all the fillings, for checking the counting against
+#[cfg(test)]
+impl SpringRow {
     fn unknown_count(&self) -> usize {
         self.springs.chars().filter(|c| *c == '?').count()
     }
 
-    fn is_valid_with(&self, fills: &String) -> Result<bool, String> {
+    fn is_valid_with(&self, fills: &str) -> bool {
         let fills = fills.chars().collect::<Vec<_>>();
         if fills.len() != self.unknown_count() {
-            return Err(format!("Invalid fill count for the spring row: {fills:?}"));
+            return false; // can't be valid if it doesn't fill all the unknowns
         }
 
         let mut fills_iter = fills.into_iter();
@@ -53,7 +290,7 @@ impl SpringRow {
         let filled_springs = filled_springs
             .chars()
             .map(|c| match c {
-                '?' => fills_iter.next().unwrap(),
+                '?' => fills_iter.next().expect("but we checked the fill count!?"),
                 _ => c,
             })
             .collect::<String>();
@@ -64,89 +301,343 @@ impl SpringRow {
             .filter(|len| *len > 0)
             .collect::<Vec<_>>();
 
-        Ok(filled_damages == self.damages)
+        filled_damages == self.damages
     }
 
-    fn all_permutations(&self) -> Result<Vec<String>, String> {
-        let permutations = (0..self.unknown_count())
+    fn all_permutations(&self) -> Vec<String> {
+        (0..self.unknown_count())
             .map(|_| vec!['.', '#'])
             .multi_cartesian_product()
             .map(|permutation| permutation.into_iter().collect::<String>())
-            .collect::<Vec<_>>();
-        Ok(permutations)
-    }
-
-    fn valid_permutations(&self) -> Result<Vec<String>, String> {
-        // #bruteforcegang 💪
-        //
-        // as it took ~30 seconds to solve the part 1,
-        // I'm not even going to try to solve the part 2 for now
-        //
-        // to solve the part 2, this needs to be optimized
-        // probably something like... filter validity after each character, caching sub-results,
-        // other dynamic programming trickery, reducing the search space, etc.
-        let permutations = self.all_permutations()?;
-        let valid_permutations = permutations
+            .collect::<Vec<_>>()
+    }
+
+    fn valid_permutations(&self) -> Vec<String> {
+        // #bruteforcegang 💪, solving the part 1 like this took ~30 seconds
+        let permutations = self.all_permutations();
+        permutations
             .into_iter()
-            .filter(|perm| self.is_valid_with(&perm).unwrap())
-            .collect::<Vec<_>>();
-        Ok(valid_permutations)
+            .filter(|perm| self.is_valid_with(perm))
+            .collect::<Vec<_>>()
+    }
+}
+
+// where the counting is: the spring index, the damaged group index and the length of
+// the damaged run so far in that group
+type CountState = (usize, usize, usize);
+
+const START: CountState = (0, 0, 0);
+
+/// Counts the arrangements of a row from any state of filling it in that the start leads to,
+/// spring by spring from the end of the row back to the start, so even rows unfolded by large
+/// factors are counted without recursing along them.
+struct Counter {
+    springs: Vec<u8>,
+    damages: Vec<usize>,
+    // by the spring index, the counts from the states the start leads to there
+    counts: Vec<HashMap<(usize, usize), u128>>,
+}
+
+impl Counter {
+    fn new(row: &SpringRow) -> Self {
+        let mut counter = Self { springs: row.springs.as_bytes().to_vec(), damages: row.damages.clone(), counts: vec![] };
+
+        // the states the start leads to, spring by spring, not counted yet
+        counter.counts.push(HashMap::from([((START.1, START.2), 0)]));
+        for spring in 0..counter.springs.len() {
+            let layer = counter.counts[spring]
+                .keys()
+                .flat_map(|(group, run)| counter.transitions((spring, *group, *run)))
+                .map(|(_, (_, group, run))| ((group, run), 0))
+                .collect();
+            counter.counts.push(layer);
+        }
+
+        // and their counts, from the ones at the end of the row back to the start
+        for spring in (0..counter.counts.len()).rev() {
+            let layer = counter.counts[spring]
+                .keys()
+                .map(|(group, run)| ((*group, *run), counter.count_from((spring, *group, *run))))
+                .collect();
+            counter.counts[spring] = layer;
+        }
+        counter
+    }
+
+    fn is_done(&self, state: CountState) -> bool {
+        state.0 == self.springs.len()
+    }
+
+    /// The states the next spring can take the filling to, with the spring it is filled with;
+    /// none that already contradict the damaged groups.
+    fn transitions(&self, state: CountState) -> Vec<(char, CountState)> {
+        let (spring, group, run) = state;
+        let Some(symbol) = self.springs.get(spring) else { return vec![]; };
+        let mut transitions = vec![];
+        if *symbol == b'#' || *symbol == b'?' {
+            // continue the damaged run, if the group has room for it
+            if group < self.damages.len() && run < self.damages[group] {
+                transitions.push(('#', (spring + 1, group, run + 1)));
+            }
+        }
+        if *symbol == b'.' || *symbol == b'?' {
+            // end the damaged run, if it completes the group
+            if run == 0 {
+                transitions.push(('.', (spring + 1, group, 0)));
+            } else if self.damages[group] == run {
+                transitions.push(('.', (spring + 1, group + 1, 0)));
+            }
+        }
+        transitions
+    }
+
+    // the count of a state from the counts of the next spring, already counted
+    fn count_from(&self, state: CountState) -> u128 {
+        if self.is_done(state) {
+            let (_, group, run) = state;
+            let all_groups_done = match run {
+                0 => group == self.damages.len(),
+                _ => group == self.damages.len() - 1 && self.damages[group] == run,
+            };
+            return if all_groups_done { 1 } else { 0 };
+        }
+        self.transitions(state)
+            .into_iter()
+            .map(|(_, next)| self.count(next))
+            .fold(0, u128::saturating_add)
+    }
+
+    /// The number of arrangements from the state, none from the states the start doesn't lead to.
+    fn count(&self, state: CountState) -> u128 {
+        let (spring, group, run) = state;
+        self.counts.get(spring).and_then(|layer| layer.get(&(group, run))).copied().unwrap_or(0)
+    }
+}
+
+/// The valid arrangements of a row, found lazily depth first; never goes down a path
+/// without arrangements at the end, so each arrangement takes a walk along the row.
+struct Arrangements {
+    counter: Counter,
+    // the states to go to, with the spring filled in on the way there
+    stack: Vec<(CountState, Option<char>)>,
+    // the springs filled in on the way to the last state gone to
+    filled: String,
+}
+
+impl Iterator for Arrangements {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((state, symbol)) = self.stack.pop() {
+            if let Some(symbol) = symbol {
+                // back to where the state branched off, the springs being all ASCII
+                self.filled.truncate(state.0 - 1);
+                self.filled.push(symbol);
+            }
+            if self.counter.is_done(state) {
+                return Some(self.filled.clone());
+            }
+            // pushed in reverse, so the arrangements come out damaged first
+            for (symbol, next) in self.counter.transitions(state).into_iter().rev() {
+                if self.counter.count(next) > 0 {
+                    self.stack.push((next, Some(symbol)));
+                }
+            }
+        }
+        None
+    }
+}
+
+// xorshift, random enough for sampling arrangements without any dependencies
+struct Random(u64);
+
+impl Random {
+    fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, limit: u128) -> u128 {
+        (((self.next() as u128) << 64) | self.next() as u128) % limit
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
     use super::*;
 
     #[test]
     fn validating_permutations() -> Result<(), String> {
         let row = SpringRow::from("???.### 1,1,3")?;
-        assert_eq!(row.is_valid_with(&"#.#".into())?, true);
-        assert_eq!(row.is_valid_with(&"##.".into())?, false);
-        assert_eq!(row.valid_permutations()?, vec!["#.#"]);
+        assert!(row.is_valid_with("#.#"));
+        assert!(!row.is_valid_with("##."));
+        assert!(!row.is_valid_with("#.#."));
+        assert_eq!(row.valid_permutations(), vec!["#.#"]);
 
         let row = SpringRow::from(".??..??...?##. 1,1,3")?;
-        assert_eq!(row.is_valid_with(&".#.#.".into())?, false);
-        assert_eq!(row.is_valid_with(&".#.##".into())?, true);
-        assert_eq!(row.valid_permutations()?.len(), 4);
+        assert!(!row.is_valid_with(".#.#."));
+        assert!(row.is_valid_with(".#.##"));
+        assert_eq!(row.valid_permutations().len(), 4);
 
         Ok(())
     }
 
+    #[test]
+    fn bad_rows_are_errors() {
+        let input = "???.### 1,1,3\n.?x 1".to_string();
+        let error = Error::parse(2, 3, "Invalid spring 'x'");
+        assert_eq!(solve_day12_part1(input), Err(error));
+        let input = "???.### 1,1,3\n.??".to_string();
+        let error = Error::parse(2, 4, "Row is missing damaged groups");
+        assert_eq!(solve_day12_part1(input), Err(error));
+        let input = "???.### 1,x,3".to_string();
+        let error = Error::parse(1, 11, "invalid digit found in string");
+        assert_eq!(solve_day12_part1(input), Err(error));
+    }
+
+    #[test]
+    fn counting_arrangements() -> Result<(), String> {
+        for (row, count) in [("???.### 1,1,3", 1), (".??..??...?##. 1,1,3", 4), ("?###???????? 3,2,1", 10)] {
+            let row = SpringRow::from(row)?;
+            assert_eq!(row.arrangement_count(), count);
+            assert_eq!(row.arrangement_count(), row.valid_permutations().len() as u128);
+        }
+        assert_eq!(SpringRow::from("### 1")?.arrangement_count(), 0);
+        assert_eq!(SpringRow::from("?###???????? 3,2,1")?.unfold(UNFOLD_FACTOR).arrangement_count(), 506250);
+        Ok(())
+    }
+
+    // the fills of the unknown springs in a filled in row, as the brute force has them
+    fn fills(row: &SpringRow, arrangement: &str) -> String {
+        row.springs.chars().zip(arrangement.chars()).filter(|(c, _)| *c == '?').map(|(_, c)| c).collect()
+    }
+
+    #[test]
+    fn streaming_arrangements() -> Result<(), String> {
+        for row in ["???.### 1,1,3", ".??..??...?##. 1,1,3", "?###???????? 3,2,1", "### 1"] {
+            let row = SpringRow::from(row)?;
+            let mut streamed = row.arrangements().map(|a| fills(&row, &a)).collect::<Vec<_>>();
+            let mut brute_force = row.valid_permutations();
+            streamed.sort();
+            brute_force.sort();
+            assert_eq!(streamed, brute_force);
+        }
+        let row = SpringRow::from(".??..??...?##. 1,1,3")?;
+        assert_eq!(row.arrangements().next(), Some(".#...#....###.".to_string()));
+
+        // lazily, so the first ones come quickly even when there are too many to list them all
+        let row = SpringRow::from("?###???????? 3,2,1")?.unfold(UNFOLD_FACTOR);
+        let first = row.arrangements().take(3).collect::<Vec<_>>();
+        assert_eq!(first.len(), 3);
+        assert!(first.iter().all(|a| row.is_valid_with(&fills(&row, a))));
+        Ok(())
+    }
+
+    #[test]
+    fn sampling_arrangements() -> Result<(), String> {
+        let row = SpringRow::from("?###???????? 3,2,1")?.unfold(UNFOLD_FACTOR);
+        let sample = row.sample_arrangements(20, 42);
+        assert_eq!(sample.len(), 20);
+        assert!(sample.iter().all(|a| row.is_valid_with(&fills(&row, a))));
+        assert_eq!(sample, row.sample_arrangements(20, 42));
+        assert_ne!(sample, row.sample_arrangements(20, 7));
+
+        // every arrangement of a small row turns up, none favored much over the others
+        let row = SpringRow::from("?###???????? 3,2,1")?;
+        let sample = row.sample_arrangements(1000, 1);
+        let counts = row.arrangements().map(|a| sample.iter().filter(|s| **s == a).count()).collect::<Vec<_>>();
+        assert_eq!(counts.len(), 10);
+        assert!(counts.iter().all(|count| (50..150).contains(count)), "{:?}", counts);
+
+        assert!(SpringRow::from("### 1")?.sample_arrangements(3, 1).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn listing_arrangements() -> Result<(), String> {
+        let input = "???.### 1,1,3\n.??..??...?##. 1,1,3".to_string();
+        assert_eq!(Arranging::new().solve(input.clone())?, solve_day12_part1(input.clone())?);
+        assert!(Arranging::new().checks_answers());
+
+        let options = [("--list".to_string(), "all".to_string())];
+        let listing = Arranging::new().with_options(&options)?;
+        assert!(!listing.checks_answers());
+        let listed = listing.solve(input.clone())?;
+        assert_eq!(listed.lines().take(3).collect::<Vec<_>>(), vec!["???.### 1,1,3", "  #.#.###", ".??..??...?##. 1,1,3"]);
+        assert_eq!(listed.lines().count(), 2 + 1 + 4);
+
+        let options = [("--list".to_string(), "3".to_string()), ("--seed".to_string(), "7".to_string())];
+        let sampled = Arranging::new().with_options(&options)?.solve(input.clone())?;
+        assert_eq!(sampled.lines().count(), 2 + 2 * 3);
+        assert_eq!(sampled, Arranging::new().with_options(&options)?.solve(input)?);
+
+        // all the ways to place two damaged springs apart in 2000 unknowns
+        let options = [("--list".to_string(), "all".to_string())];
+        let error = Error::no_solution("Too many arrangements to list, 1997001, the most is 1000000");
+        assert_eq!(Arranging::new().with_options(&options)?.solve(format!("{} 1,1", "?".repeat(2000))).err(), Some(error));
+        let options = [("--list".to_string(), "some".to_string())];
+        let error = Error::usage("Invalid --list arrangements: some");
+        assert_eq!(Arranging::new().with_options(&options).err(), Some(error));
+        Ok(())
+    }
+
     #[test]
     fn expansion_on_part2() -> Result<(), String> {
-        // ???.### 1,1,3
-        // => 5 copies =>
-        // ???.###????.###????.###????.###????.### 1,1,3,1,1,3,1,1,3,1,1,3,1,1,3
+        let row = SpringRow::from("???.### 1,1,3")?.unfold(UNFOLD_FACTOR);
+        assert_eq!(row.springs, "???.###????.###????.###????.###????.###");
+        assert_eq!(row.damages, vec![1, 1, 3, 1, 1, 3, 1, 1, 3, 1, 1, 3, 1, 1, 3]);
+        assert_eq!(row.arrangement_count(), 1);
+
+        let row = SpringRow::from(".# 1")?;
+        assert_eq!(row.unfold(1).springs, ".#");
+        assert_eq!(row.unfold(3).springs, ".#?.#?.#");
+        assert_eq!(row.unfold(3).damages, vec![1, 1, 1]);
+        let counts = (1..=5).map(|factor| SpringRow::from("?###???????? 3,2,1").map(|row| row.unfold(factor).arrangement_count()));
+        assert_eq!(counts.collect::<Result<Vec<_>, _>>()?, vec![10, 150, 2250, 33750, 506250]);
         Ok(())
     }
 
     #[test]
-    fn solve_day12_part1_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day12_part1(input)?, "21");
+    fn unfolding_by_other_factors() -> Result<(), String> {
+        let input = "???.### 1,1,3\n.??..??...?##. 1,1,3".to_string();
+        assert_eq!(Unfolding::new(1).solve(input.clone())?, solve_day12_part1(input.clone())?);
+        assert_eq!(Unfolding::new(UNFOLD_FACTOR).solve(input.clone())?, solve_day12_part2(input.clone())?);
+        assert_eq!(Unfolding::new(2).solve(input.clone())?, "33");
+        assert!(Unfolding::new(UNFOLD_FACTOR).checks_answers() && !Unfolding::new(2).checks_answers());
+        let error = Error::no_solution("Too many arrangements to count with factor 60");
+        assert_eq!(Unfolding::new(60).solve("?????????? 1".to_string()), Err(error));
+
+        let options = [("--factor".to_string(), "2".to_string())];
+        assert_eq!(Unfolding::new(UNFOLD_FACTOR).with_options(&options)?.solve(input)?, "33");
+        let options = [("--factor".to_string(), "0".to_string())];
+        let error = Error::usage("Invalid --factor copies: 0");
+        assert_eq!(Unfolding::new(UNFOLD_FACTOR).with_options(&options).err(), Some(error));
         Ok(())
     }
 
-    // #[test]
-    // fn solve_day12_part1_on_my_input() -> Result<(), String> {
-    //     let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-    //     assert_eq!(solve_day12_part1(input)?, "6871");
-    //     Ok(())
-    // }
-
-    // #[test]
-    // fn solve_day12_part2_on_example() -> Result<(), String> {
-    //     let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-    //     assert_eq!(solve_day12_part2(input)?, "#yolo");
-    //     Ok(())
-    // }
-
-    // #[test]
-    // fn solve_day12_part2_on_my_input() -> Result<(), String> {
-    //     let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-    //     assert_eq!(solve_day12_part2(input)?, "#yolo");
-    //     Ok(())
-    // }
+    #[test]
+    fn unfolding_by_huge_factors() -> Result<(), String> {
+        assert_eq!(Unfolding::new(20000).solve("???.### 1,1,3".to_string())?, "1");
+        let row = SpringRow::from("???.### 1,1,3")?.unfold(20000);
+        let expected = vec!["#.#.###"; 20000].join(".");
+        assert_eq!(row.arrangements().collect::<Vec<_>>(), vec![expected.clone()]);
+        assert_eq!(row.sample_arrangements(2, 1), vec![expected.clone(), expected]);
+        Ok(())
+    }
+
+    #[test]
+    fn solve_day12_part1_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART1)
+    }
+
+    #[test]
+    fn solve_day12_part2_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART2)
+    }
 }
@@ -1,8 +1,21 @@
 use std::collections::HashMap;
+use basics::{parsing, Error, Puzzle, Registry};
+use grid::Grid;
 
-pub fn solve_day13_part1(input: String) -> Result<String, String> {
-    let result = input
-        .split("\n\n")
+pub const PART1: Puzzle = Puzzle::new(13, 1, TITLE, solve_day13_part1);
+pub const PART2: Puzzle = Puzzle::new(13, 2, TITLE, solve_day13_part2);
+const TITLE: &str = "Point of Incidence";
+
+/// Register the solvers of the day for tooling like the `aoc` runner.
+pub fn register(registry: &mut Registry) {
+    registry.register(PART1);
+    registry.register(PART2);
+}
+
+pub fn solve_day13_part1(input: String) -> Result<String, Error> {
+    let patterns = parsing(|| parse_patterns(&input))?;
+    let result = patterns
+        .iter()
         .map(|pattern| {
             let (column_mismatches, row_mismatches) = get_mirror_mismatches(pattern);
             let vertical_mirror = column_mismatches.iter().find(|(_, miss)| **miss == 0);
@@ -19,9 +32,10 @@ pub fn solve_day13_part1(input: String) -> Result<String, String> {
     Ok(result.to_string())
 }
 
-pub fn solve_day13_part2(input: String) -> Result<String, String> {
-    let result = input
-        .split("\n\n")
+pub fn solve_day13_part2(input: String) -> Result<String, Error> {
+    let patterns = parsing(|| parse_patterns(&input))?;
+    let result = patterns
+        .iter()
         .map(|pattern| {
             let (column_mismatches, row_mismatches) = get_mirror_mismatches(pattern);
             let vertical_mirror = column_mismatches.iter().find(|(_, miss)| **miss == 1);
@@ -47,19 +61,25 @@ type RowNumber = isize;
 // number of mismatches for the given column or row mirroring
 type MismatchCount = isize;
 
-fn get_mirror_mismatches(pattern: &str) -> (
-    HashMap<ColumnNumber, MismatchCount>,
-    HashMap<RowNumber, MismatchCount>,
-) {
-    let pattern = pattern
-        .lines()
-        .map(|line| line.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
+// mismatch counts for all the column mirrorings and all the row mirrorings
+type Mismatches = (HashMap<ColumnNumber, MismatchCount>, HashMap<RowNumber, MismatchCount>);
+
+fn parse_patterns(input: &str) -> Result<Vec<Grid<char>>, Error> {
+    input
+        .split("\n\n")
+        .map(|pattern| Grid::parse(pattern, Ok).map_err(|e| e.within(input, pattern)))
+        .collect()
+}
 
-    let row_count = pattern.len() as isize;
-    let column_count = pattern[0].len() as isize;
+fn get_mirror_mismatches(pattern: &Grid<char>) -> Mismatches {
+    // mirroring rows is mirroring the columns of the transposed pattern
+    let column_mismatches = get_column_mismatches(pattern);
+    let row_mismatches = get_column_mismatches(&pattern.transpose());
+    (column_mismatches, row_mismatches)
+}
 
-    // vertical symmetry
+fn get_column_mismatches(pattern: &Grid<char>) -> HashMap<ColumnNumber, MismatchCount> {
+    let column_count = pattern.width() as isize;
     let mut column_mismatches: HashMap<ColumnNumber, MismatchCount> = HashMap::new();
     // no need to check the _last_ column since it has no right neighbor
     for column in 0..(column_count - 1) {
@@ -71,77 +91,42 @@ fn get_mirror_mismatches(pattern: &str) -> (
             if left >= right { continue; }  // left symbol is right of right symbol
             if right >= column_count { continue; }  // right symbol out of bounds
 
-            let left = left as usize;
-            let right = right as usize;
-            for row in 0..row_count {
-                let row = row as usize;
-                if pattern[row][left] != pattern[row][right] {
-                    mismatches += 1;
-                }
-            }
+            let left = pattern.column(left as usize);
+            let right = pattern.column(right as usize);
+            mismatches += left.zip(right).filter(|(l, r)| l != r).count() as isize;
         }
         // record the _column number_, not the index
         column_mismatches.insert(column + 1, mismatches);
     }
-
-    // horizontal symmetry
-    let mut row_mismatches: HashMap<RowNumber, MismatchCount> = HashMap::new();
-    // no need to check the _last_ row since it has no bottom neighbor
-    for row in 0..(row_count - 1) {
-        let mut mismatches: isize = 0;
-        for scan_offset in 0..row_count {
-            let top = row - scan_offset;
-            let bottom = row + scan_offset + 1;
-            if top < 0 { continue; } // top symbol out of bounds
-            if top >= bottom { continue; }  // top symbol is below bottom symbol
-            if bottom >= row_count { continue; }  // bottom symbol out of bounds
-
-            let top = top as usize;
-            let bottom = bottom as usize;
-            for column in 0..column_count {
-                let column = column as usize;
-                if pattern[top][column] != pattern[bottom][column] {
-                    mismatches += 1;
-                }
-            }
-        }
-        // record the _row number_, not the index
-        row_mismatches.insert(row + 1, mismatches);
-    }
-
-    (column_mismatches, row_mismatches)
+    column_mismatches
 }
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
     use super::*;
 
     #[test]
-    fn solve_day13_part1_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day13_part1(input)?, "405");
-        Ok(())
+    fn bad_patterns_are_errors() {
+        let input = "#.#\n#.#\n\n##\n#.#".to_string();
+        let error = Error::parse(5, 1, "Grid rows have different lengths");
+        assert_eq!(solve_day13_part1(input), Err(error));
     }
 
     #[test]
-    fn solve_day13_part1_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day13_part1(input)?, "35691");
+    fn solve_day13_part1_on_crlf_example() -> Result<(), String> {
+        let windows = basics::read_input_file("examples/example.txt")?.replace('\n', "\r\n");
+        let input = basics::read_input_from(windows.as_bytes())?;
+        assert_eq!(solve_day13_part1(input)?, "405");
         Ok(())
     }
 
     #[test]
-    fn solve_day13_part2_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day13_part2(input)?, "400");
-        Ok(())
+    fn solve_day13_part1_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART1)
     }
 
     #[test]
-    fn solve_day13_part2_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day13_part2(input)?, "39037");
-        Ok(())
+    fn solve_day13_part2_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART2)
     }
 }
@@ -1,77 +1,79 @@
-use std::collections::HashSet;
 use std::fmt;
+use basics::{parsing, Error, Puzzle, Registry};
+use grid::Grid;
 
-pub fn solve_day14_part1(input: String) -> Result<String, String> {
-    let mut platform = Platform::from(input.as_str());
+pub const PART1: Puzzle = Puzzle::new(14, 1, TITLE, solve_day14_part1);
+pub const PART2: Puzzle = Puzzle::new(14, 2, TITLE, solve_day14_part2);
+const TITLE: &str = "Parabolic Reflector Dish";
+
+/// Register the solvers of the day for tooling like the `aoc` runner.
+pub fn register(registry: &mut Registry) {
+    registry.register(PART1);
+    registry.register(PART2);
+}
+
+pub fn solve_day14_part1(input: String) -> Result<String, Error> {
+    let mut platform = parsing(|| Platform::from(input.as_str()))?;
     platform.tilt_north();
-    let load = calculate_load(&platform.round_rocks, platform.height);
-    Ok(load.to_string())
+    Ok(platform.load().to_string())
 }
 
-pub fn solve_day14_part2(input: String) -> Result<String, String> {
-    let mut platform = Platform::from(input.as_str());
+pub fn solve_day14_part2(input: String) -> Result<String, Error> {
+    let mut platform = parsing(|| Platform::from(input.as_str()))?;
     let load = platform.load_after_cycles(1_000_000_000);
     match load {
         Some(load) => Ok(load.to_string()),
-        None => Err("No cycles found".to_string()),
+        None => Err(Error::no_solution("No cycles found")),
     }
 }
 
-type Point = (isize, isize); // (x, y)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile { Round, Square, Empty }
 
-#[derive(Debug)]
-struct Platform {
-    round_rocks: HashSet<Point>,
-    square_rocks: HashSet<Point>,
-    width: isize,
-    height: isize,
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tile::Round => write!(f, "O"),
+            Tile::Square => write!(f, "#"),
+            Tile::Empty => write!(f, "."),
+        }
+    }
 }
 
+#[derive(Debug)]
+struct Platform(Grid<Tile>);
+
 impl fmt::Display for Platform {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut output = String::new();
-        for y in 0..self.height {
-            if y > 0 { output.push('\n'); }
-            for x in 0..self.width {
-                let symbol = if self.round_rocks.contains(&(x, y)) {
-                    'O'
-                } else if self.square_rocks.contains(&(x, y)) {
-                    '#'
-                } else {
-                    '.'
-                };
-                output.push(symbol);
-            }
-        }
-        write!(f, "{}", output)
+        write!(f, "{}", self.0)
     }
 }
 
 impl Platform {
-    fn from(input: &str) -> Self {
-        let mut round_rocks = HashSet::new();
-        let mut square_rocks = HashSet::new();
-        let mut width = 0;
-        let height = input.lines().collect::<Vec<_>>().len() as isize;
-        for (y, line) in input.lines().enumerate() {
-            for (x, symbol) in line.chars().enumerate() {
-                if symbol == 'O' {
-                    round_rocks.insert((x as isize, y as isize));
-                }
-                if symbol == '#' {
-                    square_rocks.insert((x as isize, y as isize));
-                }
-                if width == 0 { width = line.len() as isize; }
-            }
-        }
-        Platform { round_rocks, square_rocks, width, height }
+    fn from(input: &str) -> Result<Self, Error> {
+        let grid = Grid::parse(input, |symbol| match symbol {
+            'O' => Ok(Tile::Round),
+            '#' => Ok(Tile::Square),
+            '.' => Ok(Tile::Empty),
+            _ => Err(format!("Invalid symbol '{}'", symbol)),
+        })?;
+        Ok(Platform(grid))
     }
 
-    fn load_after_cycles(&mut self, cycles: usize) -> Option<isize> {
+    fn load(&self) -> usize {
+        let height = self.0.height();
+        self.0
+            .iter()
+            .filter(|(_, tile)| **tile == Tile::Round)
+            .map(|((_, y), _)| height - y)
+            .sum::<usize>()
+    }
+
+    fn load_after_cycles(&mut self, cycles: usize) -> Option<usize> {
         let mut loads = vec![];
         for curr_cycle in 0..cycles {
             self.cycle();
-            loads.insert(0, calculate_load(&self.round_rocks, self.height));
+            loads.insert(0, self.load());
             let pattern_len = detect_pattern(&loads);
             if let Some(pattern_len) = pattern_len {
                 // fast-forward ⏩️
@@ -84,90 +86,33 @@ impl Platform {
     }
 
     fn cycle(&mut self) {
-        self.tilt_north();
-        self.tilt_west();
-        self.tilt_south();
-        self.tilt_east();
-    }
-
-    fn tilt_north(&mut self) {
-        for i in 0..self.height {
-            self.round_rocks = self.round_rocks
-                .clone()
-                .into_iter()
-                .map(|(x, y)| {
-                    if y == i {
-                        for py in (0..y).rev() {
-                            if self.square_rocks.contains(&(x, py)) { return (x, py + 1); }
-                            if self.round_rocks.contains(&(x, py)) { return (x, py + 1); }
-                        }
-                        (x, 0)
-                    } else {
-                        (x, y)
-                    }
-                }).collect::<HashSet<_>>();
-        }
-    }
-
-    fn tilt_west(&mut self) {
-        for i in 0..self.width {
-            self.round_rocks = self.round_rocks
-                .clone()
-                .into_iter()
-                .map(|(x, y)| {
-                    if x == i {
-                        for px in (0..x).rev() {
-                            if self.square_rocks.contains(&(px, y)) { return (px + 1, y); }
-                            if self.round_rocks.contains(&(px, y)) { return (px + 1, y); }
-                        }
-                        (0, y)
-                    } else {
-                        (x, y)
-                    }
-                }).collect::<HashSet<_>>();
+        // rotating clockwise brings the west edge to the north, then the south and the east
+        for _ in 0..4 {
+            self.tilt_north();
+            self.0 = self.0.rotate_clockwise();
         }
     }
 
-    fn tilt_south(&mut self) {
-        for i in (0..self.height).rev() {
-            self.round_rocks = self.round_rocks
-                .clone()
-                .into_iter()
-                .map(|(x, y)| {
-                    if y == i {
-                        for ny in (y + 1)..self.height {
-                            if self.square_rocks.contains(&(x, ny)) { return (x, ny - 1); }
-                            if self.round_rocks.contains(&(x, ny)) { return (x, ny - 1); }
-                        }
-                        (x, self.height - 1)
-                    } else {
-                        (x, y)
-                    }
-                }).collect::<HashSet<_>>();
-        }
-    }
-
-    fn tilt_east(&mut self) {
-        for i in (0..self.width).rev() {
-            self.round_rocks = self.round_rocks
-                .clone()
-                .into_iter()
-                .map(|(x, y)| {
-                    if x == i {
-                        for nx in (x + 1)..self.width {
-                            if self.square_rocks.contains(&(nx, y)) { return (nx - 1, y); }
-                            if self.round_rocks.contains(&(nx, y)) { return (nx - 1, y); }
-                        }
-                        (self.width - 1, y)
-                    } else {
-                        (x, y)
+    fn tilt_north(&mut self) {
+        for x in 0..self.0.width() {
+            // where the next round rock in the column comes to rest
+            let mut rest = 0;
+            for y in 0..self.0.height() {
+                match self.0[(x, y)] {
+                    Tile::Square => rest = y + 1,
+                    Tile::Round => {
+                        self.0[(x, y)] = Tile::Empty;
+                        self.0[(x, rest)] = Tile::Round;
+                        rest += 1;
                     }
-                }).collect::<HashSet<_>>();
+                    Tile::Empty => {}
+                }
+            }
         }
     }
 }
 
-fn detect_pattern(seq: &Vec<isize>) -> Option<usize> {
+fn detect_pattern(seq: &[usize]) -> Option<usize> {
     let pattern_max_len = seq.len() / 2;
     for x in 2..pattern_max_len {
         if seq[0..x] == seq[x..2 * x] {
@@ -177,31 +122,23 @@ fn detect_pattern(seq: &Vec<isize>) -> Option<usize> {
     None
 }
 
-fn calculate_load(round_rocks: &HashSet<Point>, height: isize) -> isize {
-    round_rocks
-        .iter()
-        .map(|(_, y)| height - y)
-        .sum::<isize>()
-}
-
 #[cfg(test)]
 mod tests {
-    use std::fs;
-
     use super::*;
 
     #[test]
     fn parsing_platform() -> Result<(), String> {
-        let mut platform = Platform::from("O..\n.#.\n..O\nOO.");
-        assert_eq!(platform.round_rocks.len(), 4);
-        assert_eq!(platform.square_rocks.len(), 1);
-        assert_eq!(platform.width, 3);
-        assert_eq!(platform.height, 4);
+        let mut platform = Platform::from("O..\n.#.\n..O\nOO.")?;
+        let count = |tile: Tile| platform.0.iter().filter(|(_, t)| **t == tile).count();
+        assert_eq!(count(Tile::Round), 4);
+        assert_eq!(count(Tile::Square), 1);
+        assert_eq!(platform.0.width(), 3);
+        assert_eq!(platform.0.height(), 4);
         assert_eq!(format!("{}", platform), "O..\n.#.\n..O\nOO.".to_string());
 
         platform.tilt_north();
         assert_eq!(format!("{}", platform), "O.O\nO#.\n.O.\n...".to_string());
-        assert_eq!(calculate_load(&platform.round_rocks, platform.height), 13);
+        assert_eq!(platform.load(), 13);
 
         platform.cycle();
         assert_eq!(format!("{}", platform), "..O\nO#.\n..O\n..O".to_string());
@@ -209,32 +146,19 @@ mod tests {
     }
 
     #[test]
-    fn solve_day14_part1_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day14_part1(input)?, "136");
-        Ok(())
+    fn bad_symbols_are_errors() {
+        let input = "O..\n.#x".to_string();
+        let error = Error::parse(2, 3, "Invalid symbol 'x'");
+        assert_eq!(solve_day14_part1(input), Err(error));
     }
 
     #[test]
-    fn solve_day14_part1_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day14_part1(input)?, "108826");
-        Ok(())
+    fn solve_day14_part1_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART1)
     }
 
     #[test]
-    fn solve_day14_part2_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day14_part2(input)?, "64");
-        Ok(())
+    fn solve_day14_part2_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART2)
     }
-
-    // #[test]
-    // fn solve_day14_part2_on_my_input() -> Result<(), String> {
-    //     let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-    //     assert_eq!(solve_day14_part2(input)?, "#yolo");
-    //     // 100106, 99704 and 99318 are too high...
-    //     // the example works, but my input doesn't, so there probably is a bug somewhere 🤷
-    //     Ok(())
-    // }
 }
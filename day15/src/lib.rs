@@ -1,40 +1,71 @@
-use std::iter::repeat;
+use basics::{parsing, Error, Puzzle, Registry};
 
-pub fn solve_day15_part1(input: String) -> Result<String, String> {
-    Ok(hash_initialization_sequence(&input).to_string())
+pub const PART1: Puzzle = Puzzle::new(15, 1, TITLE, solve_day15_part1);
+pub const PART2: Puzzle = Puzzle::new(15, 2, TITLE, solve_day15_part2);
+const TITLE: &str = "Lens Library";
+
+/// Register the solvers of the day for tooling like the `aoc` runner.
+pub fn register(registry: &mut Registry) {
+    registry.register(PART1);
+    registry.register(PART2);
+}
+
+pub fn solve_day15_part1(input: String) -> Result<String, Error> {
+    let steps = parsing(|| input.split(',').collect::<Vec<_>>());
+    Ok(hash_initialization_sequence(&steps).to_string())
+}
+
+pub fn solve_day15_part2(input: String) -> Result<String, Error> {
+    let steps = parsing(|| parse_steps(&input))?;
+    Ok(facility_focusing_power(&steps).to_string())
 }
 
-pub fn solve_day15_part2(input: String) -> Result<String, String> {
-    Ok(facility_focusing_power(input)?.to_string())
+/// A step of the initialization sequence, by the label of the lens it's about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step<'a> {
+    /// Put the lens with the focal length in its box, replacing the one with the same label.
+    Insert(&'a str, u8),
+    /// Take the lens out of its box, if it's there.
+    Remove(&'a str),
 }
 
-fn facility_focusing_power(input: String) -> Result<usize, String> {
-    let mut boxes = repeat::<Vec<Lens>>(vec![])
-        .take(256)
-        .collect::<Vec<_>>();
-    for step in input.split(',') {
-        if step.contains('=') {
-            let mut parts = step.split('=');
-            let lens_label = parts.next().ok_or("no lens label!?")?;
-            let box_index = hash_single(lens_label) as usize;
-            let focal_length = parts.next()
-                .ok_or("no lens focal length!?")?
-                .parse::<u8>()
-                .map_err(|e| e.to_string())?;
-            let lens = Lens::new(lens_label, focal_length);
-            let r#box = boxes.get_mut(box_index).ok_or(format!("no box at {box_index:}"))?;
-            if let Some(old_lens) = r#box.iter_mut().find(|l| l.label == lens.label) {
-                old_lens.focal_length = lens.focal_length;
+fn parse_steps(input: &str) -> Result<Vec<Step<'_>>, Error> {
+    input
+        .split(',')
+        .map(|step| {
+            if let Some((lens_label, focal_length_str)) = step.split_once('=') {
+                let focal_length = focal_length_str
+                    .parse::<u8>()
+                    .map_err(|e| Error::parse_at(input, focal_length_str, format!("Bad focal length, {}", e)))?;
+                Ok(Step::Insert(lens_label, focal_length))
+            } else if let Some((lens_label, _)) = step.split_once('-') {
+                Ok(Step::Remove(lens_label))
             } else {
-                r#box.push(lens);
+                Err(Error::parse_at(input, step, "Step has no '=' or '-' operation"))
+            }
+        })
+        .collect()
+}
+
+fn facility_focusing_power(steps: &[Step<'_>]) -> usize {
+    let mut boxes: Vec<Vec<Lens>> = vec![vec![]; 256];
+    for step in steps {
+        match *step {
+            Step::Insert(lens_label, focal_length) => {
+                let box_index = hash_single(lens_label) as usize;
+                let lens = Lens::new(lens_label, focal_length);
+                let r#box = boxes.get_mut(box_index).expect("but there is a box for every hash!?");
+                if let Some(old_lens) = r#box.iter_mut().find(|l| l.label == lens.label) {
+                    old_lens.focal_length = lens.focal_length;
+                } else {
+                    r#box.push(lens);
+                }
+            }
+            Step::Remove(lens_label) => {
+                let box_index = hash_single(lens_label) as usize;
+                let r#box = boxes.get_mut(box_index).expect("but there is a box for every hash!?");
+                r#box.retain(|existing| existing.label != lens_label);
             }
-        }
-        if step.contains('-') {
-            let mut parts = step.split('-');
-            let lens_label = parts.next().ok_or("no lens label!?")?;
-            let box_index = hash_single(lens_label) as usize;
-            let r#box = boxes.get_mut(box_index).ok_or(format!("no box at {box_index:?}!?"))?;
-            r#box.retain(|existing| existing.label != lens_label);
         }
     }
     let facility_focusing_power = boxes
@@ -54,12 +85,12 @@ fn facility_focusing_power(input: String) -> Result<usize, String> {
             box_focusing_power
         })
         .sum();
-    Ok(facility_focusing_power)
+    facility_focusing_power
 }
 
-fn hash_initialization_sequence(text: &str) -> usize {
-    text
-        .split(',')
+fn hash_initialization_sequence(steps: &[&str]) -> usize {
+    steps
+        .iter()
         .map(|piece| hash_single(piece) as usize)
         .sum()
 }
@@ -85,7 +116,6 @@ impl Lens {
 
 #[cfg(test)]
 mod tests {
-    use std::fs;
     use super::*;
 
     #[test]
@@ -94,30 +124,22 @@ mod tests {
     }
 
     #[test]
-    fn solve_day15_part1_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day15_part1(input)?, "1320");
-        Ok(())
-    }
-
-    #[test]
-    fn solve_day15_part1_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day15_part1(input)?, "510801");
-        Ok(())
+    fn bad_steps_are_errors() {
+        let input = "rn=1,cm-,qp=x".to_string();
+        let error = Error::parse(1, 13, "Bad focal length, invalid digit found in string");
+        assert_eq!(solve_day15_part2(input), Err(error));
+        let input = "rn=1,cm,qp=3".to_string();
+        let error = Error::parse(1, 6, "Step has no '=' or '-' operation");
+        assert_eq!(solve_day15_part2(input), Err(error));
     }
 
     #[test]
-    fn solve_day15_part2_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day15_part2(input)?, "145");
-        Ok(())
+    fn solve_day15_part1_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART1)
     }
 
     #[test]
-    fn solve_day15_part2_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day15_part2(input)?, "212763");
-        Ok(())
+    fn solve_day15_part2_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART2)
     }
 }
@@ -1,6 +1,6 @@
-fn main() -> Result<(), String> {
-    let input = basics::read_input()?;
-    let result = day18::solve_day18_part1(input)?;
-    println!("{}", result);
-    Ok(())
+use std::process::ExitCode;
+use day18::Rendering;
+
+fn main() -> ExitCode {
+    basics::run(Rendering::new())
 }
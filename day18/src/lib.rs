@@ -1,142 +1,213 @@
-use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
+use std::fs;
+use basics::{parsing, Error, Puzzle, Registry, Solver, SolverOption};
+use grid::{Direction, Grid, Polygon};
 
-pub fn solve_day18_part1(input: String) -> Result<String, String> {
-    let plan = DigPlan::from_text(&input)?;
-    let _map = DigMap::from_plan(plan)?;
-    Ok("#yolo".to_string())
-}
+mod render;
+
+pub const PART1: Puzzle = Puzzle::new(18, 1, TITLE, solve_day18_part1);
+pub const PART2: Puzzle = Puzzle::new(18, 2, TITLE, solve_day18_part2);
+const TITLE: &str = "Lavaduct Lagoon";
 
-pub fn solve_day18_part2(input: String) -> Result<String, String> {
-    Ok(input)
+/// Register the solvers of the day for tooling like the `aoc` runner.
+pub fn register(registry: &mut Registry) {
+    registry.register(Rendering::new());
+    registry.register(PART2);
 }
 
-#[derive(Debug, Clone)]
-struct DigMap {
-    edges: Vec<Vec<Option<String>>>,
+pub fn solve_day18_part1(input: String) -> Result<String, Error> {
+    let plan = parsing(|| DigPlan::from_text(&input, Reading::Plain))?;
+    Ok(plan.lagoon_volume()?.to_string())
 }
 
-impl Deref for DigMap {
-    type Target = Vec<Vec<Option<String>>>;
-    fn deref(&self) -> &Self::Target { &self.edges }
+pub fn solve_day18_part2(input: String) -> Result<String, Error> {
+    let plan = parsing(|| DigPlan::from_text(&input, Reading::Hex))?;
+    Ok(plan.lagoon_volume()?.to_string())
 }
 
-fn some_neighbors(point: Point, edges: &Vec<Vec<Option<String>>>) -> Vec<Point> {
-    neighbors(point)
-        .into_iter()
-        .filter(|(x, y)| {
-            if *x < 0 || *y < 0 { return false; }
-            let Some(row) = edges.get(*y as usize) else { return false; };
-            let Some(cell) = row.get(*x as usize) else { return false; };
-            return cell.is_some();
-        })
-        .collect()
+/// The part 1 solver drawing the lagoon rather than measuring it, in the terminal with
+/// `--render ansi` or as an image file with e.g. `--render png --output lagoon.png`.
+#[derive(Debug, Clone, Default)]
+pub struct Rendering {
+    format: Option<ImageFormat>,
+    output_path: Option<String>,
 }
 
-fn neighbors(point: Point) -> Vec<Point> {
-    let (x, y) = point;
-    vec![
-        (x + 1, y),
-        (x, y + 1),
-        (x - 1, y),
-        (x, y - 1),
-    ]
+/// The image formats the lagoon can be rendered to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat { Ansi, Ppm, Png }
+
+impl Rendering {
+    pub fn new() -> Self {
+        Self::default()
+    }
 }
 
-impl DigMap {
-    fn from_plan(plan: DigPlan) -> Result<Self, String> {
-        let mut map = HashMap::new();
-        let mut position: Point = (0, 0);
-        let mut start_direction: Option<Direction> = None;
-        for step in plan.iter() {
-            if start_direction.is_none() {
-                start_direction = Some(step.direction.clone());
-            }
-            for _ in 0..step.length {
-                match step.direction {
-                    Direction::Up => position.1 -= 1,
-                    Direction::Right => position.0 += 1,
-                    Direction::Down => position.1 += 1,
-                    Direction::Left => position.0 -= 1,
-                }
-                map.insert(position, step.color.clone());
+impl Solver for Rendering {
+    fn day(&self) -> u8 { 18 }
+    fn part(&self) -> u8 { 1 }
+    fn title(&self) -> &str { TITLE }
+
+    fn solve(&self, input: String) -> Result<String, Error> {
+        let Some(format) = self.format else {
+            return solve_day18_part1(input);
+        };
+        let image = render_lagoon(&input, format)?;
+        match &self.output_path {
+            Some(path) => {
+                fs::write(path, &image).map_err(|e| Error::io(format!("{}: {}", path, e)))?;
+                Ok(format!("Rendered the lagoon to {}", path))
             }
+            None => Ok(String::from_utf8(image).expect("but only ANSI goes to stdout!?")),
         }
+    }
 
-        // normalize minimum to (0, 0)
-        let min_x = map.keys().map(|(x, _)| x).min().ok_or("No min x")?.to_owned();
-        let min_y = map.keys().map(|(_, y)| y).min().ok_or("No min y")?.to_owned();
-        map = map
-            .into_iter()
-            .map(|((x, y), color)| ((x - min_x, y - min_y), color))
-            .collect();
-
-        // convert to vector
-        let mut edges = Vec::new();
-        let max_x = map.keys().map(|(x, _)| x).max().ok_or("No max x")?.to_owned();
-        let max_y = map.keys().map(|(_, y)| y).max().ok_or("No max y")?.to_owned();
-        for y in 0..=max_y {
-            let mut row = Vec::new();
-            for x in 0..=max_x {
-                if let Some(color) = map.get(&(x, y)) {
-                    row.push(Some(color.to_owned()));
-                } else {
-                    row.push(None);
+    fn checks_answers(&self) -> bool { self.format.is_none() }
+    fn options(&self) -> &[SolverOption] { &[RENDER_OPTION, OUTPUT_OPTION] }
+
+    fn with_options(&self, options: &[(String, String)]) -> Result<Box<dyn Solver>, Error> {
+        let mut rendering = self.clone();
+        for (option, value) in options {
+            match option.as_str() {
+                "--output" => rendering.output_path = Some(value.clone()),
+                _ => {
+                    rendering.format = Some(match value.as_str() {
+                        "ansi" => ImageFormat::Ansi,
+                        "ppm" => ImageFormat::Ppm,
+                        "png" => ImageFormat::Png,
+                        _ => return Err(Error::usage(format!("Invalid --render format: {}", value))),
+                    });
                 }
             }
-            edges.push(row);
         }
+        match (rendering.format, &rendering.output_path) {
+            (None, Some(_)) => Err(Error::usage("Please provide the --render format of the --output")),
+            (Some(ImageFormat::Ppm | ImageFormat::Png), None) => {
+                Err(Error::usage("Please provide the --output file of the image"))
+            }
+            _ => Ok(Box::new(rendering)),
+        }
+    }
+}
 
-        let start_point = (min_x.wrapping_neg(), min_y.wrapping_neg());
-        let start_direction = start_direction.ok_or("No start direction")?;
-        let next = match start_direction {
-            Direction::Up => (start_point.0, start_point.1 - 1),
-            Direction::Right => (start_point.0 + 1, start_point.1),
-            Direction::Down => (start_point.0, start_point.1 + 1),
-            Direction::Left => (start_point.0 - 1, start_point.1),
-        };
-        let mut route = vec![start_point, next];
-        loop {
-            let last = route.last().ok_or("No last route point")?.to_owned();
-            let neighbors = some_neighbors(last, &edges);
-            if neighbors.len() != 2 { panic!("{neighbors:?}"); }
-            let next = if !route.contains(&neighbors[0]) {
-                Some(neighbors[0])
-            } else if !route.contains(&neighbors[1]) {
-                Some(neighbors[1])
-            } else {
-                None
-            };
-            let Some(next) = next else {
-                break;
-            };
-            route.push(next);
+const RENDER_OPTION: SolverOption = SolverOption {
+    name: "--render",
+    value: "<ansi | ppm | png>",
+    help: "draw the lagoon with the trench colors instead of measuring it,\n\
+           ansi needing a terminal with 24-bit colors",
+};
+
+const OUTPUT_OPTION: SolverOption = SolverOption {
+    name: "--output",
+    value: "<file>",
+    help: "write the rendering to the file, needed for the images",
+};
+
+/// Render the lagoon of the plan, read as in part 1, with the trench cells painted in their
+/// colors and the interior filled, e.g. for seeing what went wrong with a plan.
+pub fn render_lagoon(input: &str, format: ImageFormat) -> Result<Vec<u8>, Error> {
+    let plan = DigPlan::from_text(input, Reading::Plain)?;
+    let pixels = DigMap::from_plan(plan)?.pixels();
+    Ok(match format {
+        ImageFormat::Ansi => render::to_ansi(&pixels).into_bytes(),
+        ImageFormat::Ppm => render::to_ppm(&pixels),
+        ImageFormat::Png => render::to_png(&pixels),
+    })
+}
+
+// large enough for any part 1 plan, but not for the part 2 distances
+const MAX_MAP_CELLS: usize = 10_000_000;
+
+/// The trench cells with their colors, for seeing the lagoon; the solvers only need the corners.
+#[derive(Debug, Clone)]
+struct DigMap {
+    edges: Grid<Option<String>>,
+}
+
+impl Deref for DigMap {
+    type Target = Grid<Option<String>>;
+    fn deref(&self) -> &Self::Target { &self.edges }
+}
+
+impl DigMap {
+    fn from_plan(plan: DigPlan) -> Result<Self, Error> {
+        // size the map from the corners before drawing a single cell of it
+        if plan.is_empty() {
+            return Err(empty_plan());
+        }
+        let corners = plan.corners()?;
+        let min_x = corners.iter().map(|(x, _)| *x).min().expect("but there's the start!?");
+        let min_y = corners.iter().map(|(_, y)| *y).min().expect("but there's the start!?");
+        let max_x = corners.iter().map(|(x, _)| *x).max().expect("but there's the start!?");
+        let max_y = corners.iter().map(|(_, y)| *y).max().expect("but there's the start!?");
+        let width = max_x.abs_diff(min_x) as u128 + 1;
+        let height = max_y.abs_diff(min_y) as u128 + 1;
+        if width.saturating_mul(height) > MAX_MAP_CELLS as u128 {
+            let message = format!("Dig map of {}x{} cells is too large to draw", width, height);
+            return Err(Error::no_solution(message));
         }
 
+        let mut edges = Grid::new(width as usize, height as usize, None);
+        let mut position = ((-min_x) as usize, (-min_y) as usize);
+        for step in plan.iter() {
+            let (dx, dy) = step.direction.offset();
+            for _ in 0..step.length {
+                position = (position.0.wrapping_add_signed(dx), position.1.wrapping_add_signed(dy));
+                edges[position] = Some(step.color.clone());
+            }
+        }
 
-        let _start = (start_point, start_direction);
         Ok(Self { edges })
     }
 
+    #[cfg(test)]
     fn edge_volume(&self) -> usize {
-        self.iter()
-            .map(|row| row.iter().filter(|cell| cell.is_some()).count())
-            .sum()
+        self.iter().filter(|(_, cell)| cell.is_some()).count()
     }
 
-    fn print_edges(&self) {
-        for row in self.iter() {
-            for cell in row.iter() {
-                match cell {
-                    Some(_color) => print!("#"),
-                    None => print!("."),
-                }
+    /// Which cells are inside the trench loop, flood filling the ground from the map edges.
+    fn interior(&self) -> Grid<bool> {
+        let mut ground = self.map(|_| false);
+        let mut to_check = self
+            .points()
+            .filter(|(x, y)| *x == 0 || *y == 0 || *x == self.width() - 1 || *y == self.height() - 1)
+            .collect::<Vec<_>>();
+        while let Some(point) = to_check.pop() {
+            if ground[point] || self[point].is_some() {
+                continue;
             }
-            println!();
+            ground[point] = true;
+            to_check.extend(self.neighbors4(point));
         }
+        let mut interior = self.map(|cell| cell.is_none());
+        for (point, outside) in ground.iter() {
+            if *outside {
+                interior[point] = false;
+            }
+        }
+        interior
+    }
+
+    /// The trench cells in their colors, the interior and the ground around as pixels.
+    fn pixels(&self) -> Grid<render::Rgb> {
+        let interior = self.interior();
+        let mut pixels = self.map(|cell| match cell {
+            Some(color) => render::parse_color(color).unwrap_or(render::INTERIOR),
+            None => render::GROUND,
+        });
+        for (point, inside) in interior.iter() {
+            if *inside {
+                pixels[point] = render::INTERIOR;
+            }
+        }
+        pixels
     }
 }
 
+fn empty_plan() -> Error {
+    Error::no_solution("Dig plan has no steps")
+}
+
 #[derive(Debug, Clone)]
 struct DigPlan(Vec<DigStep>);
 
@@ -146,151 +217,237 @@ impl Deref for DigPlan {
 }
 
 impl DigPlan {
-    fn from_text(text: &str) -> Result<Self, String> {
+    fn from_text(text: &str, reading: Reading) -> Result<Self, Error> {
         let steps = text
             .lines()
-            .map(|line| DigStep::from_text(line))
+            .map(|line| DigStep::from_text(line, reading).map_err(|e| e.within(text, line)))
             .collect::<Result<Vec<_>, _>>()?;
         Ok(Self(steps))
     }
 
-    fn get_sideness(&self) -> Side {
-        let degrees = self
-            .windows(2)
-            .map(|w| {
-                let degrees = match (&w[0].direction, &w[1].direction) {
-                    (Direction::Up, Direction::Right) => 90,
-                    (Direction::Up, Direction::Left) => -90,
-                    (Direction::Right, Direction::Up) => -90,
-                    (Direction::Right, Direction::Down) => 90,
-                    (Direction::Down, Direction::Right) => -90,
-                    (Direction::Down, Direction::Left) => 90,
-                    (Direction::Left, Direction::Up) => 90,
-                    (Direction::Left, Direction::Down) => -90,
-                    _ => 0,
-                };
-                degrees
-            })
-            .sum::<isize>();
-        if degrees == 270 || degrees == 450 { // missing 90 or -90
-            Side::Right
-        } else if degrees == -270 || degrees == -450 {  // missing -90 or 90
-            Side::Left
-        } else {
-            panic!("Invalid route turns degree sum: {}", degrees);
+    /// The cubic meters of the lagoon, the trench and its interior.
+    fn lagoon_volume(&self) -> Result<u128, Error> {
+        self.lagoon()?
+            .lattice_points()
+            .ok_or_else(|| Error::no_solution("Lagoon is too large to measure"))
+    }
+
+    /// The corners the plan digs to from the start at `(0, 0)`, starting and ending with it
+    /// when the trench returns to the start.
+    fn corners(&self) -> Result<Vec<(i64, i64)>, Error> {
+        let mut corners = vec![(0_i64, 0_i64)];
+        for step in self.iter() {
+            let (x, y) = corners[corners.len() - 1];
+            let (dx, dy) = step.direction.offset();
+            let corner = x.checked_add(dx as i64 * step.length).zip(y.checked_add(dy as i64 * step.length));
+            corners.push(corner.ok_or_else(|| Error::no_solution("Trench goes too far from the start to measure"))?);
         }
+        Ok(corners)
+    }
+
+    /// The lagoon dug along the trench, as the polygon through the corners of the plan.
+    fn lagoon(&self) -> Result<Polygon, Error> {
+        if self.is_empty() {
+            return Err(empty_plan());
+        }
+        let end = *self.corners()?.last().ok_or_else(empty_plan)?;
+        if end != (0, 0) {
+            let message = format!("Trench doesn't return to the start, it ends at {:?}", end);
+            return Err(Error::no_solution(message));
+        }
+        let lagoon = Polygon::from_steps((0, 0), self.iter().map(|step| (step.direction, step.length)));
+        if !lagoon.is_simple() {
+            return Err(Error::no_solution("Trench doesn't form a simple loop, it crosses or retraces itself"));
+        }
+        Ok(lagoon)
     }
 }
 
+/// How to read the dig steps: part 1 reads the direction and the length as they are,
+/// part 2 finds that they were swapped with the ones hidden in the color 🤦
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Reading { Plain, Hex }
+
 #[derive(Debug, Clone)]
 struct DigStep {
     direction: Direction,
-    length: usize,
+    length: i64,
     color: String,
 }
 
 impl DigStep {
-    fn from_text(text: &str) -> Result<Self, String> {
+    fn from_text(text: &str, reading: Reading) -> Result<Self, Error> {
         let mut parts = text.split_whitespace();
         let direction = match parts.next() {
             Some("R") => Direction::Right,
             Some("L") => Direction::Left,
             Some("U") => Direction::Up,
             Some("D") => Direction::Down,
-            Some(other) => return Err(format!("Invalid direction: {}", other)),
-            None => return Err("No direction found".to_string()),
+            Some(other) => return Err(Error::parse_at(text, other, format!("Invalid direction: {}", other))),
+            None => return Err(Error::parse_at_end(text, "No direction found")),
         };
         let length = match parts.next() {
-            Some(length) => length.parse::<usize>().map_err(|e| e.to_string())?,
-            None => return Err("No length found".to_string()),
+            Some(length) => {
+                let meters = length
+                    .parse::<usize>()
+                    .map_err(|e| Error::parse_at(text, length, format!("Bad length, {}", e)))?;
+                i64::try_from(meters).map_err(|_| Error::parse_at(text, length, "Bad length, too long to dig"))?
+            }
+            None => return Err(Error::parse_at_end(text, "No length found")),
         };
         let color = match parts.next() {
-            Some(color) => color.to_string().trim_matches(|c| c == '(' || c == ')').to_string(),
-            None => return Err("No color found".to_string()),
+            Some(color) => color.trim_matches(|c| c == '(' || c == ')'),
+            None => return Err(Error::parse_at_end(text, "No color found")),
+        };
+        let (direction, length) = match reading {
+            Reading::Plain => (direction, length),
+            Reading::Hex => decode_color(color).map_err(|e| e.within(text, color))?,
         };
-        Ok(Self { direction, length, color })
+        Ok(Self { direction, length, color: color.to_string() })
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-enum Direction { Up, Right, Down, Left }
-
-impl Direction {
-    fn turn_to(&self, sideness: &Side) -> Direction {
-        match (self, sideness) {
-            (Direction::Up, Side::Left) => Direction::Left,
-            (Direction::Up, Side::Right) => Direction::Right,
-            (Direction::Right, Side::Left) => Direction::Up,
-            (Direction::Right, Side::Right) => Direction::Down,
-            (Direction::Down, Side::Left) => Direction::Right,
-            (Direction::Down, Side::Right) => Direction::Left,
-            (Direction::Left, Side::Left) => Direction::Down,
-            (Direction::Left, Side::Right) => Direction::Up,
-        }
+/// The direction and the length hidden in the color like `#70c710`: the first five hex digits
+/// are the length and the last digit is the direction, 0 right, 1 down, 2 left and 3 up.
+fn decode_color(color: &str) -> Result<(Direction, i64), Error> {
+    let digits = color.strip_prefix('#').unwrap_or_default();
+    if digits.len() != 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(Error::parse_at(color, color, format!("Invalid color: {}", color)));
     }
+    let length = i64::from_str_radix(&digits[..5], 16).expect("but we checked the hex digits!?");
+    let direction = match &digits[5..] {
+        "0" => Direction::Right,
+        "1" => Direction::Down,
+        "2" => Direction::Left,
+        "3" => Direction::Up,
+        other => return Err(Error::parse_at(color, other, format!("Invalid direction digit: {}", other))),
+    };
+    Ok((direction, length))
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-enum Side { Left, Right }
-
-type Point = (isize, isize); // (x, y)
-
 #[cfg(test)]
 mod tests {
-    use std::fs;
     use super::*;
 
     #[test]
     fn test_stuff() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        let plan = DigPlan::from_text(&input)?;
+        let input = basics::read_input_file("examples/example.txt")?;
+        let plan = DigPlan::from_text(&input, Reading::Plain)?;
         let map = DigMap::from_plan(plan)?;
         assert_eq!(map.edge_volume(), 38);
-        map.print_edges();
+        let interior = map.interior();
+        assert_eq!(interior.iter().filter(|(_, inside)| **inside).count(), 62 - 38);
+        let ansi = String::from_utf8(render_lagoon(&input, ImageFormat::Ansi)?).unwrap_or_default();
+        assert_eq!(ansi.lines().count(), 10);
+        assert!(ansi.starts_with("\x1b[38;2;122;33;227m██")); // the last step, U 2 (#7a21e3)
+
+        let input = "R 300000000 (#000000)\nD 1 (#000000)\nL 300000000 (#000000)\nU 1 (#000000)";
+        let error = Error::no_solution("Dig map of 300000001x2 cells is too large to draw");
+        assert_eq!(render_lagoon(input, ImageFormat::Ppm), Err(error));
+        Ok(())
+    }
+
+    #[test]
+    fn rendering_through_options() -> Result<(), String> {
+        let input = basics::read_input_file("examples/example.txt")?;
+        assert_eq!(Rendering::new().solve(input.clone())?, solve_day18_part1(input.clone())?);
+        assert!(Rendering::new().checks_answers());
+
+        let options = [("--render".to_string(), "ansi".to_string())];
+        let rendering = Rendering::new().with_options(&options)?;
+        assert!(!rendering.checks_answers());
+        assert_eq!(rendering.solve(input)?.lines().count(), 10);
+
+        let options = [("--render".to_string(), "png".to_string())];
+        let error = Error::usage("Please provide the --output file of the image");
+        assert_eq!(Rendering::new().with_options(&options).err(), Some(error));
+        let options = [("--output".to_string(), "lagoon.txt".to_string())];
+        let error = Error::usage("Please provide the --render format of the --output");
+        assert_eq!(Rendering::new().with_options(&options).err(), Some(error));
+        let options = [("--render".to_string(), "gif".to_string())];
+        let error = Error::usage("Invalid --render format: gif");
+        assert_eq!(Rendering::new().with_options(&options).err(), Some(error));
+        Ok(())
+    }
+
+    #[test]
+    fn digging_lagoons() -> Result<(), String> {
+        let plan = DigPlan::from_text("R 2 (#000000)\nD 2 (#000000)\nL 2 (#000000)\nU 2 (#000000)", Reading::Plain)?;
+        assert_eq!(plan.lagoon_volume()?, 9);
+        let plan = DigPlan::from_text("R 2 (#000000)\nD 2 (#000000)", Reading::Plain)?;
+        let error = Error::no_solution("Trench doesn't return to the start, it ends at (2, 2)");
+        assert_eq!(plan.lagoon(), Err(error));
+
+        let input = "R 3 (#000000)\nL 1 (#000000)\nL 2 (#000000)".to_string();
+        let error = Error::no_solution("Trench doesn't form a simple loop, it crosses or retraces itself");
+        assert_eq!(solve_day18_part1(input), Err(error));
+        let input = "R 2 (#000000)\nD 2 (#000000)\nL 1 (#000000)\nU 3 (#000000)\nL 1 (#000000)\nD 1 (#000000)".to_string();
+        assert!(solve_day18_part1(input).is_err()); // crossing itself
         Ok(())
     }
 
     #[test]
     fn test_dig_plan() -> Result<(), String> {
-        let plan = DigPlan::from_text("R 6 (#70c710)\nD 5 (#0dc571)");
+        let plan = DigPlan::from_text("R 6 (#70c710)\nD 5 (#0dc571)", Reading::Plain);
         assert_eq!(plan?.len(), 2);
         Ok(())
     }
 
     #[test]
     fn test_dig_step() -> Result<(), String> {
-        let step = DigStep::from_text("R 6 (#70c710)")?;
+        let step = DigStep::from_text("R 6 (#70c710)", Reading::Plain)?;
         assert_eq!(step.direction, Direction::Right);
         assert_eq!(step.length, 6);
         assert_eq!(step.color, "#70c710");
+
+        let step = DigStep::from_text("R 6 (#70c710)", Reading::Hex)?;
+        assert_eq!(step.direction, Direction::Right);
+        assert_eq!(step.length, 461937);
+        assert_eq!(step.color, "#70c710");
+        let step = DigStep::from_text("U 2 (#7a21e3)", Reading::Hex)?;
+        assert_eq!((step.direction, step.length), (Direction::Up, 500254));
         Ok(())
     }
 
     #[test]
-    fn solve_day18_part1_on_example() -> Result<(), String> {
-        let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day18_part1(input)?, "#yolo");
-        Ok(())
+    fn bad_dig_steps_are_errors() {
+        let input = "R 6 (#70c710)\nX 5 (#0dc571)".to_string();
+        let error = Error::parse(2, 1, "Invalid direction: X");
+        assert_eq!(solve_day18_part1(input), Err(error));
+        let input = "R 6 (#70c710)\nD -5 (#0dc571)".to_string();
+        let error = Error::parse(2, 3, "Bad length, invalid digit found in string");
+        assert_eq!(solve_day18_part1(input), Err(error));
+        let input = "R 6 (#70c710)\nD 5".to_string();
+        let error = Error::parse(2, 4, "No color found");
+        assert_eq!(solve_day18_part1(input), Err(error));
+        let input = "R 6 (#70c710)\nD 5 (#0dc5z1)".to_string();
+        let error = Error::parse(2, 6, "Invalid color: #0dc5z1");
+        assert_eq!(solve_day18_part2(input), Err(error));
+        let input = "R 6 (#70c714)\nD 5 (#0dc571)".to_string();
+        let error = Error::parse(1, 12, "Invalid direction digit: 4");
+        assert_eq!(solve_day18_part2(input), Err(error));
+        let input = "R 6 (#70c710)\nD 9223372036854775808 (#0dc571)".to_string();
+        let error = Error::parse(2, 3, "Bad length, too long to dig");
+        assert_eq!(solve_day18_part1(input), Err(error));
     }
 
     #[test]
-    fn solve_day18_part1_on_my_input() -> Result<(), String> {
-        let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-        assert_eq!(solve_day18_part1(input)?, "#yolo");
-        Ok(())
+    fn huge_lagoons() {
+        let input = "R 5000000000000 (#000000)\nD 5000000000000 (#000000)\n\
+            L 5000000000000 (#000000)\nU 5000000000000 (#000000)".to_string();
+        assert_eq!(solve_day18_part1(input), Ok("25000000000010000000000001".to_string()));
+        let input = "R 9223372036854775807 (#000000)\nR 1 (#000000)".to_string();
+        let error = Error::no_solution("Trench goes too far from the start to measure");
+        assert_eq!(solve_day18_part1(input), Err(error));
     }
 
-    // #[test]
-    // fn solve_day18_part2_on_example() -> Result<(), String> {
-    //     let input = fs::read_to_string("examples/example.txt").map_err(|e| e.to_string())?;
-    //     assert_eq!(solve_day18_part2(input)?, "#yolo");
-    //     Ok(())
-    // }
-
-    // #[test]
-    // fn solve_day18_part2_on_my_input() -> Result<(), String> {
-    //     let input = fs::read_to_string("examples/ruksi.txt").map_err(|e| e.to_string())?;
-    //     assert_eq!(solve_day18_part2(input)?, "#yolo");
-    //     Ok(())
-    // }
+    #[test]
+    fn solve_day18_part1_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART1)
+    }
+
+    #[test]
+    fn solve_day18_part2_on_answered_inputs() -> Result<(), String> {
+        basics::check_answers(&PART2)
+    }
 }
@@ -0,0 +1,155 @@
+use std::fmt::Write;
+use grid::Grid;
+
+/// A color as `(red, green, blue)`.
+pub type Rgb = (u8, u8, u8);
+
+/// The color of the dug out interior of the lagoon.
+pub const INTERIOR: Rgb = (96, 96, 96);
+
+/// The color of the ground around the lagoon.
+pub const GROUND: Rgb = (0, 0, 0);
+
+/// Parse a color like `#70c710`, or `None` if it is not one.
+pub fn parse_color(color: &str) -> Option<Rgb> {
+    let digits = color.strip_prefix('#')?;
+    if digits.len() != 6 || !digits.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&digits[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Render the pixels as a binary PPM image, the simplest image format there is.
+pub fn to_ppm(pixels: &Grid<Rgb>) -> Vec<u8> {
+    let mut ppm = format!("P6\n{} {}\n255\n", pixels.width(), pixels.height()).into_bytes();
+    for (_, (r, g, b)) in pixels.iter() {
+        ppm.extend([*r, *g, *b]);
+    }
+    ppm
+}
+
+/// Render the pixels as a PNG image, stored uncompressed so it needs no compression library.
+pub fn to_png(pixels: &Grid<Rgb>) -> Vec<u8> {
+    let mut raw = Vec::with_capacity((pixels.width() * 3 + 1) * pixels.height());
+    for row in pixels.rows() {
+        raw.push(0); // no filter
+        for (r, g, b) in row {
+            raw.extend([*r, *g, *b]);
+        }
+    }
+
+    let mut header = vec![];
+    header.extend((pixels.width() as u32).to_be_bytes());
+    header.extend((pixels.height() as u32).to_be_bytes());
+    header.extend([8, 2, 0, 0, 0]); // 8 bits per channel, RGB, no interlacing
+
+    let mut png = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+    push_chunk(&mut png, b"IHDR", &header);
+    push_chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    push_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+/// Render the pixels for a terminal supporting 24-bit colors, two characters per pixel
+/// so the lagoon isn't squashed; the ground is left blank.
+pub fn to_ansi(pixels: &Grid<Rgb>) -> String {
+    let mut ansi = String::new();
+    for row in pixels.rows() {
+        for (r, g, b) in row {
+            match (*r, *g, *b) == GROUND {
+                true => ansi.push_str("  "),
+                false => write!(ansi, "\x1b[38;2;{};{};{}m██", r, g, b).expect("but it's a String!?"),
+            }
+        }
+        ansi.push_str("\x1b[0m\n");
+    }
+    ansi
+}
+
+fn push_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend(kind);
+    png.extend(data);
+    let crc = crc32(&png[start..]);
+    png.extend(crc.to_be_bytes());
+}
+
+// zlib stream of uncompressed deflate blocks, each at most 65535 bytes
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut zlib = vec![0x78, 0x01];
+    let blocks = data.chunks(65535).collect::<Vec<_>>();
+    let blocks = if blocks.is_empty() { vec![&data[..0]] } else { blocks };
+    for (i, block) in blocks.iter().enumerate() {
+        zlib.push(if i == blocks.len() - 1 { 1 } else { 0 }); // the final block flag
+        let length = block.len() as u16;
+        zlib.extend(length.to_le_bytes());
+        zlib.extend((!length).to_le_bytes());
+        zlib.extend(*block);
+    }
+    zlib.extend(adler32(data).to_be_bytes());
+    zlib
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffff_u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1_u32, 0_u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pixels() -> Grid<Rgb> {
+        Grid::from_rows(vec![vec![(255, 0, 0), GROUND], vec![INTERIOR, (0, 0, 255)]])
+            .expect("but the rows are the same length!?")
+    }
+
+    #[test]
+    fn parsing_colors() {
+        assert_eq!(parse_color("#70c710"), Some((0x70, 0xc7, 0x10)));
+        assert_eq!(parse_color("70c710"), None);
+        assert_eq!(parse_color("#70c71"), None);
+        assert_eq!(parse_color("#70c7zz"), None);
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn rendering_images() {
+        let ppm = to_ppm(&pixels());
+        assert!(ppm.starts_with(b"P6\n2 2\n255\n"));
+        assert_eq!(ppm.len(), 11 + 2 * 2 * 3);
+
+        let png = to_png(&pixels());
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], [0, 0, 0, 2, 0, 0, 0, 2]);
+        assert!(png.ends_with(&[b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+
+        let ansi = to_ansi(&pixels());
+        assert_eq!(ansi.lines().count(), 2);
+        assert!(ansi.starts_with("\x1b[38;2;255;0;0m██  \x1b[0m\n"));
+    }
+}
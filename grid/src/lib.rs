@@ -0,0 +1,266 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use basics::Error;
+
+pub use geometry::Direction;
+pub use polygon::{Polygon, Vertex};
+
+mod geometry;
+mod polygon;
+
+/// A position in a grid as `(x, y)`, i.e. `(column, row)`, with `(0, 0)` at the top left.
+pub type Point = (usize, usize);
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self where T: Clone {
+        Self { cells: vec![fill; width * height], width, height }
+    }
+
+    /// Build a grid from its rows, or `None` if the rows have different lengths.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        Some(Self { cells: rows.into_iter().flatten().collect(), width, height })
+    }
+
+    /// Parse a grid from lines of text, one cell per character.
+    ///
+    /// The `cell` function converts the characters, its error message is reported
+    /// at the offending character.
+    pub fn parse(text: &str, cell: impl Fn(char) -> Result<T, String>) -> Result<Self, Error> {
+        let width = text.lines().next().map_or(0, |line| line.chars().count());
+        if width == 0 {
+            return Err(Error::parse_at_end(text, "No cells in the grid"));
+        }
+        let mut cells = vec![];
+        let mut height = 0;
+        for line in text.lines() {
+            if line.chars().count() != width {
+                return Err(Error::parse_at(text, line, "Grid rows have different lengths"));
+            }
+            for (i, c) in line.char_indices() {
+                cells.push(cell(c).map_err(|message| Error::parse_at(text, &line[i..], message))?);
+            }
+            height += 1;
+        }
+        Ok(Self { cells, width, height })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        point.0 < self.width && point.1 < self.height
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        if !self.contains(point) { return None; }
+        self.cells.get(point.1 * self.width + point.0)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        if !self.contains(point) { return None; }
+        self.cells.get_mut(point.1 * self.width + point.0)
+    }
+
+    /// Replace the cell at the point, returning the old value, or `None` if out of bounds.
+    pub fn set(&mut self, point: Point, value: T) -> Option<T> {
+        self.get_mut(point).map(|cell| std::mem::replace(cell, value))
+    }
+
+    /// The point `(dx, dy)` away from the given point, if it is within the grid.
+    pub fn offset(&self, point: Point, dx: isize, dy: isize) -> Option<Point> {
+        let x = point.0.checked_add_signed(dx)?;
+        let y = point.1.checked_add_signed(dy)?;
+        if self.contains((x, y)) { Some((x, y)) } else { None }
+    }
+
+    /// The next point in the direction, if it is within the grid.
+    pub fn step(&self, point: Point, direction: Direction) -> Option<Point> {
+        let (dx, dy) = direction.offset();
+        self.offset(point, dx, dy)
+    }
+
+    /// The up, right, down and left neighbors of the point within the grid, in that order.
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item=Point> + '_ {
+        self.directed_neighbors(point).map(|(_, neighbor)| neighbor)
+    }
+
+    /// Like `neighbors4`, with the direction of each neighbor.
+    pub fn directed_neighbors(&self, point: Point) -> impl Iterator<Item=(Direction, Point)> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(point, direction).map(|p| (direction, p)))
+    }
+
+    /// The neighbors of the point within the grid, diagonals included, clockwise from up.
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item=Point> + '_ {
+        [(0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1)]
+            .into_iter()
+            .filter_map(move |(dx, dy)| self.offset(point, dx, dy))
+    }
+
+    /// All the points of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item=Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All the cells of the grid with their points, row by row.
+    pub fn iter(&self) -> impl Iterator<Item=(Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The first point, row by row, whose cell matches the predicate.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Point> {
+        self.iter().find(|(_, cell)| predicate(cell)).map(|(point, _)| point)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item=&[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item=&T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1)).take(self.height)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item=impl Iterator<Item=&T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid { cells: self.cells.iter().map(f).collect(), width: self.width, height: self.height }
+    }
+
+    /// Mirror the grid over its top-left to bottom-right diagonal, i.e. columns become rows.
+    pub fn transpose(&self) -> Self where T: Clone {
+        let cells = self.columns().flat_map(|column| column.cloned()).collect();
+        Self { cells, width: self.height, height: self.width }
+    }
+
+    /// Rotate the grid a quarter turn clockwise, i.e. the left column becomes the top row.
+    pub fn rotate_clockwise(&self) -> Self where T: Clone {
+        let cells = self.columns().flat_map(|column| column.cloned().collect::<Vec<_>>().into_iter().rev()).collect();
+        Self { cells, width: self.height, height: self.width }
+    }
+
+    /// Rotate the grid a quarter turn counterclockwise, i.e. the right column becomes the top row.
+    pub fn rotate_counterclockwise(&self) -> Self where T: Clone {
+        let cells = (0..self.width).rev().flat_map(|x| self.column(x).cloned()).collect();
+        Self { cells, width: self.height, height: self.width }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point).unwrap_or_else(|| panic!("Point {:?} is outside the grid", point))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(point).unwrap_or_else(|| panic!("Point {:?} is outside the grid", point))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 { writeln!(f)?; }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters() -> Result<Grid<char>, Error> {
+        Grid::parse("abc\ndef", Ok)
+    }
+
+    #[test]
+    fn parsing_grids() -> Result<(), String> {
+        let grid = letters()?;
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get((2, 1)), Some(&'f'));
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid[(0, 1)], 'd');
+        assert_eq!(grid.to_string(), "abc\ndef");
+        assert_eq!(Grid::from_rows(vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']]), Some(grid));
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), None);
+
+        let error = Error::parse(2, 1, "Grid rows have different lengths");
+        assert_eq!(Grid::parse("ab\nc", Ok), Err(error));
+        let error = Error::parse(2, 2, "Invalid cell 'x'");
+        let digits = Grid::parse("12\n3x", |c| c.to_digit(10).ok_or(format!("Invalid cell '{}'", c)));
+        assert_eq!(digits, Err(error));
+        assert!(Grid::parse("", Ok).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn changing_cells() -> Result<(), String> {
+        let mut grid = Grid::new(2, 2, '.');
+        assert_eq!(grid.set((1, 0), '#'), Some('.'));
+        assert_eq!(grid.set((2, 0), '#'), None);
+        grid[(0, 1)] = 'O';
+        assert_eq!(grid.to_string(), ".#\nO.");
+        assert_eq!(grid.position(|c| *c == 'O'), Some((0, 1)));
+        assert_eq!(grid.map(|c| *c == '.').iter().filter(|(_, empty)| **empty).count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn finding_neighbors() -> Result<(), String> {
+        let grid = letters()?;
+        assert_eq!(grid.neighbors4((0, 0)).collect::<Vec<_>>(), vec![(1, 0), (0, 1)]);
+        assert_eq!(grid.neighbors4((1, 1)).collect::<Vec<_>>(), vec![(1, 0), (2, 1), (0, 1)]);
+        assert_eq!(grid.neighbors8((1, 1)).collect::<Vec<_>>(), vec![(1, 0), (2, 0), (2, 1), (0, 1), (0, 0)]);
+        assert_eq!(grid.offset((2, 1), -2, -1), Some((0, 0)));
+        assert_eq!(grid.offset((2, 1), 1, 0), None);
+        assert_eq!(grid.step((2, 1), Direction::Up), Some((2, 0)));
+        let directed = grid.directed_neighbors((0, 1)).collect::<Vec<_>>();
+        assert_eq!(directed, vec![(Direction::Up, (0, 0)), (Direction::Right, (1, 1))]);
+        Ok(())
+    }
+
+    #[test]
+    fn iterating_and_turning() -> Result<(), String> {
+        let grid = letters()?;
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.columns().map(|c| c.collect::<String>()).collect::<Vec<_>>(), vec!["ad", "be", "cf"]);
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+        Ok(())
+    }
+}
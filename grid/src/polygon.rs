@@ -0,0 +1,202 @@
+use std::cmp::Ordering;
+use crate::Direction;
+
+/// A corner of a polygon as `(x, y)`, signed and wide enough for coordinates far off any grid.
+pub type Vertex = (i64, i64);
+
+/// A simple polygon with its corners on the integer lattice, e.g. a loop walked on a grid.
+///
+/// Measures the polygon from its vertices alone, so the coordinates can be as large as
+/// they like without allocating anything in proportion to them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon(Vec<Vertex>);
+
+impl Polygon {
+    /// The polygon through the vertices in order, closing from the last back to the first.
+    pub fn new(vertices: Vec<Vertex>) -> Self {
+        Self(vertices)
+    }
+
+    /// The polygon walked from the start in steps of the given direction and length,
+    /// which must keep the corners within the `i64` coordinates.
+    pub fn from_steps(start: Vertex, steps: impl IntoIterator<Item=(Direction, i64)>) -> Self {
+        let mut position = start;
+        let mut vertices = vec![start];
+        for (direction, length) in steps {
+            let (dx, dy) = direction.offset();
+            position = (position.0 + dx as i64 * length, position.1 + dy as i64 * length);
+            vertices.push(position);
+        }
+        if vertices.len() > 1 && vertices.last() == vertices.first() {
+            vertices.pop();
+        }
+        Self(vertices)
+    }
+
+    pub fn vertices(&self) -> &[Vertex] {
+        &self.0
+    }
+
+    // each edge as (from, to), including the closing one
+    fn edges(&self) -> impl Iterator<Item=(&Vertex, &Vertex)> {
+        self.0.iter().zip(self.0.iter().skip(1).chain(self.0.first()))
+    }
+
+    /// Twice the area enclosed by the vertices, with the shoelace formula;
+    /// doubled so it stays an integer. `None` when it's too large even for an `i128`.
+    pub fn double_area(&self) -> Option<u128> {
+        self.edges()
+            .try_fold(0_i128, |sum, ((x1, y1), (x2, y2))| {
+                let cross = (*x1 as i128 * *y2 as i128).checked_sub(*x2 as i128 * *y1 as i128)?;
+                sum.checked_add(cross)
+            })
+            .map(i128::unsigned_abs)
+    }
+
+    /// The number of lattice points on the edges, i.e. the length of the boundary
+    /// when the edges are horizontal or vertical.
+    pub fn boundary_points(&self) -> u128 {
+        self.edges()
+            .map(|((x1, y1), (x2, y2))| gcd(x2.abs_diff(*x1), y2.abs_diff(*y1)) as u128)
+            .sum()
+    }
+
+    /// The number of lattice points strictly inside, with Pick's theorem `A = I + B/2 - 1`;
+    /// `None` when the theorem can't apply, e.g. when the edges retrace each other,
+    /// or when the area is too large to measure.
+    pub fn interior_points(&self) -> Option<u128> {
+        if self.0.len() < 3 {
+            return Some(0);
+        }
+        let twice = self.double_area()?.checked_add(2)?.checked_sub(self.boundary_points())?;
+        Some(twice / 2)
+    }
+
+    /// The number of lattice points inside or on the edges, e.g. the tiles a dug loop covers.
+    pub fn lattice_points(&self) -> Option<u128> {
+        self.interior_points()?.checked_add(self.boundary_points())
+    }
+
+    /// Whether the edges form a single loop that doesn't touch itself, i.e. only the edges
+    /// next to each other meet, at their shared corner, and none of them has zero length.
+    pub fn is_simple(&self) -> bool {
+        let edges = self.edges().collect::<Vec<_>>();
+        if edges.len() < 3 || edges.iter().any(|(from, to)| from == to) {
+            return false;
+        }
+        for (i, (a1, a2)) in edges.iter().enumerate() {
+            for (j, (b1, b2)) in edges.iter().enumerate().skip(i + 1) {
+                let meets = if j == i + 1 {
+                    // a2 is b1, the other ends must not lie along the other edge
+                    on_segment(a1, a2, b2) || on_segment(b1, b2, a1)
+                } else if i == 0 && j == edges.len() - 1 {
+                    // b2 is a1, likewise
+                    on_segment(a1, a2, b1) || on_segment(b1, b2, a2)
+                } else {
+                    segments_meet(a1, a2, b1, b2)
+                };
+                if meets {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+// which side of the line from a to b the point p is on, as the sign of the cross product
+fn orientation(a: &Vertex, b: &Vertex, p: &Vertex) -> i8 {
+    let [ax, ay, bx, by, px, py] = [a.0, a.1, b.0, b.1, p.0, p.1].map(i128::from);
+    // the differences fit in 65 bits, but their products only as magnitudes in a u128
+    let product = |u: i128, v: i128| (u.signum() * v.signum(), u.unsigned_abs() * v.unsigned_abs());
+    let (left_sign, left) = product(bx - ax, py - ay);
+    let (right_sign, right) = product(by - ay, px - ax);
+    if left_sign != right_sign {
+        return (left_sign - right_sign).signum() as i8;
+    }
+    match left.cmp(&right) {
+        Ordering::Less => -left_sign as i8,
+        Ordering::Equal => 0,
+        Ordering::Greater => left_sign as i8,
+    }
+}
+
+fn on_segment(a: &Vertex, b: &Vertex, p: &Vertex) -> bool {
+    orientation(a, b, p) == 0
+        && a.0.min(b.0) <= p.0 && p.0 <= a.0.max(b.0)
+        && a.1.min(b.1) <= p.1 && p.1 <= a.1.max(b.1)
+}
+
+// whether the segments cross or touch anywhere
+fn segments_meet(a1: &Vertex, a2: &Vertex, b1: &Vertex, b2: &Vertex) -> bool {
+    let crossing = orientation(a1, a2, b1) * orientation(a1, a2, b2) < 0
+        && orientation(b1, b2, a1) * orientation(b1, b2, a2) < 0;
+    crossing || on_segment(a1, a2, b1) || on_segment(a1, a2, b2) || on_segment(b1, b2, a1) || on_segment(b1, b2, a2)
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measuring_polygons() {
+        // a 4x3 rectangle, like # around . in a 5 by 4 tile grid
+        let rectangle = Polygon::new(vec![(0, 0), (4, 0), (4, 3), (0, 3)]);
+        assert_eq!(rectangle.double_area(), Some(24));
+        assert_eq!(rectangle.boundary_points(), 14);
+        assert_eq!(rectangle.interior_points(), Some(6));
+        assert_eq!(rectangle.lattice_points(), Some(20));
+
+        let triangle = Polygon::new(vec![(0, 0), (4, 0), (0, 4)]);
+        assert_eq!(triangle.double_area(), Some(16));
+        assert_eq!(triangle.boundary_points(), 12);
+        assert_eq!(triangle.interior_points(), Some(3));
+
+        assert_eq!(Polygon::new(vec![(0, 0), (3, 0)]).interior_points(), Some(0));
+        // retracing itself, there's no area for the boundary
+        assert_eq!(Polygon::new(vec![(0, 0), (3, 0), (2, 0)]).interior_points(), None);
+    }
+
+    #[test]
+    fn measuring_huge_polygons() {
+        let side = 5_000_000_000_000;
+        let square = Polygon::new(vec![(0, 0), (side, 0), (side, side), (0, side)]);
+        assert_eq!(square.double_area(), Some(2 * 25_000_000_000_000_000_000_000_000));
+        assert_eq!(square.boundary_points(), 4 * 5_000_000_000_000);
+        assert_eq!(square.lattice_points(), Some(25_000_000_000_010_000_000_000_001));
+
+        let side = i64::MAX;
+        let square = Polygon::new(vec![(-side, -side), (side, -side), (side, side), (-side, side)]);
+        assert_eq!(square.double_area(), None);
+        assert_eq!(square.interior_points(), None);
+        assert!(square.is_simple());
+    }
+
+    #[test]
+    fn walking_polygons() {
+        use Direction::*;
+        let steps = [(Right, 4), (Down, 3), (Left, 4), (Up, 3)];
+        let walked = Polygon::from_steps((0, 0), steps);
+        assert_eq!(walked, Polygon::new(vec![(0, 0), (4, 0), (4, 3), (0, 3)]));
+
+        // the orientation doesn't matter
+        let steps = [(Down, 3), (Right, 4), (Up, 3), (Left, 4)];
+        assert_eq!(Polygon::from_steps((0, 0), steps).lattice_points(), Some(20));
+    }
+
+    #[test]
+    fn simple_polygons() {
+        assert!(Polygon::new(vec![(0, 0), (4, 0), (4, 3), (0, 3)]).is_simple());
+        assert!(Polygon::new(vec![(0, 0), (4, 0), (0, 4)]).is_simple());
+        // retracing, a figure eight, touching itself and too few corners
+        assert!(!Polygon::new(vec![(0, 0), (3, 0), (2, 0)]).is_simple());
+        assert!(!Polygon::new(vec![(0, 0), (2, 2), (2, 0), (0, 2)]).is_simple());
+        assert!(!Polygon::new(vec![(0, 0), (4, 0), (4, 2), (2, 2), (2, 0), (2, -2), (0, -2)]).is_simple());
+        assert!(!Polygon::new(vec![(0, 0), (3, 0)]).is_simple());
+        assert!(!Polygon::new(vec![(0, 0), (0, 0), (3, 0), (0, 3)]).is_simple());
+    }
+}